@@ -220,8 +220,21 @@ function main(): void {
 }
 
 export { batch_files, parse_files };
-export { parse, parse_batch } from "./pkg/liquid_docs.js";
-export type { FileInput, LiquidFile, ParseResult } from "./pkg/liquid_docs.js";
+export {
+	parse,
+	parse_batch,
+	parse_batch_streaming,
+	parse_batch_with_progress,
+	get_allowed_shopify_objects,
+	version,
+	compute_stats,
+} from "./pkg/liquid_docs.js";
+export type {
+	BatchStats,
+	FileInput,
+	LiquidFile,
+	ParseResult,
+} from "./pkg/liquid_docs.js";
 
 // Run if executed directly
 if (require.main === module) {
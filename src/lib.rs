@@ -1,49 +1,534 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod liquid_docs;
 mod shopify_liquid_objects;
 
+use alloc::{
+	borrow::Cow,
+	boxed::Box,
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
 use tsify::Tsify;
+#[cfg(feature = "std")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "std")]
+use web_sys::{ReadableStream, ReadableStreamDefaultController};
 
-pub use liquid_docs::LiquidDocs;
+pub use liquid_docs::{LiquidDocs, ParseWarning, ParsingError, ParsingErrorWithContext};
 
 /// The return type for [parse_files]
-#[derive(Debug, Serialize, Deserialize, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "std",
+	derive(Tsify),
+	tsify(into_wasm_abi, from_wasm_abi, hashmap_as_object)
+)]
 pub struct LiquidFile {
 	pub path: String,
 	pub liquid_types: Option<ParseResult>,
+	/// Wall-clock nanoseconds it took to parse this file, only set when [ParserConfig::measure_timing] is enabled
+	pub parse_duration_ns: Option<u64>,
+}
+
+impl LiquidFile {
+	/// Whether the file contains at least one successfully parsed doc block, a file with only errors is not documented
+	pub fn is_documented(&self) -> bool {
+		self.liquid_types.as_ref().is_some_and(|result| !result.success.is_empty())
+	}
+
+	/// Whether any doc block in the file failed to parse, use together with [LiquidFile::is_documented] to find clean files
+	pub fn has_parse_errors(&self) -> bool {
+		self.liquid_types.as_ref().is_some_and(|result| !result.errors.is_empty())
+	}
+
+	/// Split the file into its path and parse result without cloning either
+	pub fn into_path_and_result(self) -> (String, Option<ParseResult>) {
+		(self.path, self.liquid_types)
+	}
+
+	/// `1.0` when the file contains at least one successfully parsed doc block and `0.0` otherwise
+	pub fn doc_coverage_ratio(&self) -> f64 {
+		if self.is_documented() { 1.0 } else { 0.0 }
+	}
+}
+
+/// Share of files with at least one successfully parsed doc block between `0.0` and `1.0`, `1.0` for an empty batch
+pub fn batch_coverage(files: &[LiquidFile]) -> f64 {
+	if files.is_empty() {
+		return 1.0;
+	}
+
+	files.iter().map(LiquidFile::doc_coverage_ratio).sum::<f64>() / files.len() as f64
+}
+
+/// A compact overview of a batch for CI dashboards, files count as documented by [LiquidFile::is_documented] so the
+/// numbers match [compute_batch_stats]
+pub fn to_summary_json(files: &[LiquidFile]) -> serde_json::Value {
+	let documented = files.iter().filter(|file| file.is_documented()).count();
+	let errors = files
+		.iter()
+		.filter_map(|file| {
+			let result = file.liquid_types.as_ref().filter(|result| !result.errors.is_empty())?;
+			Some(serde_json::json!({ "path": file.path, "errors": result.errors }))
+		})
+		.collect::<Vec<_>>();
+
+	serde_json::json!({
+		"total": files.len(),
+		"documented": documented,
+		"undocumented": files.len() - documented,
+		"errors": errors,
+	})
+}
+
+/// Totals of a batch, files count as documented when they contain at least one successfully parsed doc block
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct BatchStats {
+	pub total_files: usize,
+	pub documented_files: usize,
+	pub undocumented_files: usize,
+	/// Files with at least one doc block that failed to parse
+	pub error_files: usize,
+	pub total_params: usize,
+	pub total_examples: usize,
+	pub total_errors: usize,
+}
+
+/// Aggregate the [BatchStats] of a [parse_batch] result
+pub fn compute_batch_stats(files: &[LiquidFile]) -> BatchStats {
+	let mut stats = BatchStats {
+		total_files: files.len(),
+		..Default::default()
+	};
+
+	for file in files {
+		if file.is_documented() {
+			stats.documented_files += 1;
+		}
+		if file.has_parse_errors() {
+			stats.error_files += 1;
+		}
+		if let Some(result) = &file.liquid_types {
+			stats.total_params += result.total_param_count();
+			stats.total_examples += result.success.iter().map(|doc_block| doc_block.example.len()).sum::<usize>();
+			stats.total_errors += result.errors.len();
+		}
+	}
+	stats.undocumented_files = stats.total_files - stats.documented_files;
+
+	stats
 }
 
 /// The error type for [parse]
-#[derive(Debug, Serialize, Deserialize, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct ParseError {
 	line: usize,
 	column: usize,
 	message: String,
+	code: String,
+}
+
+impl core::fmt::Display for ParseError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}:{}: {}", self.line, self.column, self.message)
+	}
+}
+
+impl core::error::Error for ParseError {}
+
+impl From<ParsingError> for ParseError {
+	fn from(error: ParsingError) -> Self {
+		ParseError {
+			line: error.get_line(),
+			column: error.get_column(),
+			message: error.to_string(),
+			code: String::from(error.get_code()),
+		}
+	}
+}
+
+impl From<ParseWarning> for ParseError {
+	fn from(warning: ParseWarning) -> Self {
+		ParseError {
+			line: warning.get_line(),
+			column: warning.get_column(),
+			message: warning.to_string(),
+			code: String::from(warning.get_code()),
+		}
+	}
 }
 
 /// The return type for [parse]
-#[derive(Debug, Serialize, Deserialize, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "std",
+	derive(Tsify),
+	tsify(into_wasm_abi, from_wasm_abi, hashmap_as_object)
+)]
 pub struct ParseResult {
 	pub success: Vec<DocBlock>,
 	pub errors: Vec<ParseError>,
+	pub warnings: Vec<ParseError>,
+}
+
+impl ParseResult {
+	/// Number of doc blocks that parsed successfully
+	pub fn success_count(&self) -> usize {
+		self.success.len()
+	}
+
+	/// Number of doc blocks that failed to parse
+	pub fn error_count(&self) -> usize {
+		self.errors.len()
+	}
+
+	/// Share of doc blocks that parsed successfully between `0.0` and `1.0`, `1.0` when there were no doc blocks at all
+	pub fn coverage_ratio(&self) -> f64 {
+		let total = self.success_count() + self.error_count();
+		if total == 0 {
+			1.0
+		} else {
+			self.success_count() as f64 / total as f64
+		}
+	}
+
+	/// Find the first doc block whose description contains `query`, ignoring case
+	pub fn find_block_by_description(&self, query: &str) -> Option<&DocBlock> {
+		let query = query.to_lowercase();
+		self.success.iter().find(|block| block.description.to_lowercase().contains(&query))
+	}
+
+	/// Find all doc blocks that document a param with the given name
+	pub fn find_blocks_with_param(&self, param_name: &str) -> Vec<&DocBlock> {
+		self.success.iter().filter(|block| block.contains_param(param_name)).collect()
+	}
+
+	/// Number of params across all successfully parsed doc blocks
+	pub fn total_param_count(&self) -> usize {
+		self.success.iter().map(DocBlock::param_count).sum()
+	}
+
+	/// Take the successfully parsed doc blocks and explicitly discard all errors and warnings
+	pub fn into_vec(self) -> Vec<DocBlock> {
+		self.success
+	}
+}
+
+impl IntoIterator for ParseResult {
+	type Item = Result<DocBlock, String>;
+	type IntoIter = core::iter::Chain<
+		core::iter::Map<alloc::vec::IntoIter<DocBlock>, fn(DocBlock) -> Self::Item>,
+		core::iter::Map<alloc::vec::IntoIter<ParseError>, fn(ParseError) -> Self::Item>,
+	>;
+
+	/// Yield all successfully parsed doc blocks first and then the messages of all errors
+	fn into_iter(self) -> Self::IntoIter {
+		let success: fn(DocBlock) -> Self::Item = Ok;
+		let errors: fn(ParseError) -> Self::Item = |error| Err(error.message);
+		self.success.into_iter().map(success).chain(self.errors.into_iter().map(errors))
+	}
+}
+
+impl<'a> IntoIterator for &'a ParseResult {
+	type Item = Result<&'a DocBlock, &'a str>;
+	type IntoIter = core::iter::Chain<
+		core::iter::Map<core::slice::Iter<'a, DocBlock>, fn(&'a DocBlock) -> Self::Item>,
+		core::iter::Map<core::slice::Iter<'a, ParseError>, fn(&'a ParseError) -> Self::Item>,
+	>;
+
+	/// Yield all successfully parsed doc blocks first and then the messages of all errors
+	fn into_iter(self) -> Self::IntoIter {
+		let success: fn(&'a DocBlock) -> Self::Item = Ok;
+		let errors: fn(&'a ParseError) -> Self::Item = |error| Err(error.message.as_str());
+		self.success.iter().map(success).chain(self.errors.iter().map(errors))
+	}
 }
 
 /// The three different things Shopify supports inside doc tags
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+///
+/// Fields serialize in declaration order so the JSON always reads `description`, `deprecated`, `param`, `param_groups`,
+/// `links` and then `example`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct DocBlock {
 	pub description: String,
+	pub deprecated: Option<DeprecatedInfo>,
 	pub param: Vec<Param>,
-	pub example: Vec<String>,
+	pub param_groups: Vec<ParamGroup>,
+	pub links: Vec<DocLink>,
+	pub example: Vec<ExampleBlock>,
+}
+
+impl DocBlock {
+	/// Create a doc block with just a description, chain [DocBlock::with_param] and [DocBlock::with_example] to add more
+	pub fn with_description(description: impl Into<String>) -> Self {
+		Self {
+			description: description.into(),
+			..Default::default()
+		}
+	}
+
+	/// Append a param
+	pub fn with_param(mut self, param: Param) -> Self {
+		self.param.push(param);
+		self
+	}
+
+	/// Append an example with the given body and an empty span
+	pub fn with_example(mut self, body: impl Into<String>) -> Self {
+		self.example.push(ExampleBlock {
+			body: body.into(),
+			span: Span::default(),
+		});
+		self
+	}
+
+	/// The description up to the first line break e.g. for a hover summary, the whole description if it is a single line
+	pub fn description_first_line(&self) -> &str {
+		self.description.split('\n').next().unwrap_or_default()
+	}
+
+	/// Check if a param with the given name is documented
+	pub fn contains_param(&self, name: &str) -> bool {
+		self.param.iter().any(|param| param.name == name)
+	}
+
+	/// Number of documented params
+	pub fn param_count(&self) -> usize {
+		self.param.len()
+	}
+
+	/// Iterate over the bodies of all examples without their spans
+	pub fn example_bodies(&self) -> impl Iterator<Item = &str> {
+		self.example.iter().map(|example| example.body.as_str())
+	}
+
+	/// The body of the first example e.g. for hover documentation
+	pub fn first_example(&self) -> Option<&str> {
+		self.example.first().map(|example| example.body.as_str())
+	}
+
+	/// Check if at least one example is documented
+	pub fn has_examples(&self) -> bool {
+		!self.example.is_empty()
+	}
+
+	/// All example bodies with a `{% raw %}` block that wraps the whole body replaced by its dedented content, bodies
+	/// that are only partly raw are returned as they are
+	pub fn example_unwrapped_raw(&self) -> Vec<String> {
+		self
+			.example_bodies()
+			.map(|body| LiquidDocs::unwrap_raw_example(body).unwrap_or_else(|| String::from(body)))
+			.collect()
+	}
+
+	/// Apply `f` to every param, grouped params are transformed the same way so both views stay in sync
+	pub fn map_params(mut self, f: impl Fn(Param) -> Param) -> DocBlock {
+		self.param = self.param.into_iter().map(&f).collect();
+		for group in &mut self.param_groups {
+			group.params = core::mem::take(&mut group.params).into_iter().map(&f).collect();
+		}
+		self
+	}
+
+	/// Keep only the params for which `f` returns true, in both the flat list and the groups
+	pub fn filter_params(mut self, f: impl Fn(&Param) -> bool) -> DocBlock {
+		self.param.retain(&f);
+		for group in &mut self.param_groups {
+			group.params.retain(&f);
+		}
+		self
+	}
+
+	/// All params, param groups and links of both doc blocks, params `other` shares by name with `self` are taken from
+	/// `self` and so are links to the same URL. Everything else like the description and examples comes from `self`.
+	pub fn union(&self, other: &DocBlock) -> DocBlock {
+		let mut union = self.clone();
+		union.param.extend(other.param.iter().filter(|param| !self.contains_param(&param.name)).cloned());
+
+		for other_group in &other.param_groups {
+			let params = other_group.params.iter().filter(|param| !self.contains_param(&param.name)).cloned();
+			match union.param_groups.iter_mut().find(|group| group.name == other_group.name) {
+				Some(group) => group.params.extend(params),
+				None => union.param_groups.push(ParamGroup {
+					name: other_group.name.clone(),
+					params: params.collect(),
+				}),
+			}
+		}
+
+		union
+			.links
+			.extend(other.links.iter().filter(|link| !self.links.iter().any(|self_link| self_link.url == link.url)).cloned());
+		union
+	}
+
+	/// Only the params of `self` that `other` documents by name as well. Everything else like the description and
+	/// examples comes from `self`.
+	pub fn intersection(&self, other: &DocBlock) -> DocBlock {
+		self.clone().filter_params(|param| other.contains_param(&param.name))
+	}
+
+	/// Walk the description, all params and all example bodies in order with the given visitor
+	pub fn accept(&self, visitor: &mut impl DocBlockVisitor) {
+		visitor.visit_description(&self.description);
+		for param in &self.param {
+			visitor.visit_param(param);
+		}
+		for example in self.example_bodies() {
+			visitor.visit_example(example);
+		}
+	}
+
+	/// Collect the unique variable paths used inside `{{ }}` output tags of all examples e.g. `variant.price`
+	pub fn template_variables(&self) -> Vec<String> {
+		let mut variables = Vec::new();
+
+		for example in self.example_bodies() {
+			let mut rest = example;
+			while let Some(start) = rest.find("{{") {
+				let expression = &rest[start + 2..];
+				let end = expression.find("}}").unwrap_or(expression.len());
+				collect_template_variables(&expression[..end], &mut variables);
+				rest = &expression[end..];
+			}
+		}
+
+		variables
+	}
+
+	/// Generate a `{% render %}` call for this snippet with placeholder values for each param. Optional params are listed
+	/// in a `{% # optional: %}` comment below the call.
+	pub fn to_render_call(&self, snippet_name: &str) -> String {
+		let mut render_call = format!("{{% render '{snippet_name}'");
+		let mut optional_params = Vec::new();
+
+		for param in &self.param {
+			let placeholder = match &param.type_ {
+				Some(ParamType::String) => "''",
+				Some(ParamType::Number) => "0",
+				Some(ParamType::Boolean) => "false",
+				_ => "nil",
+			};
+
+			if param.optional {
+				optional_params.push(format!("{}: {placeholder}", param.name));
+			} else {
+				render_call.push_str(&format!(", {}: {placeholder}", param.name));
+			}
+		}
+		render_call.push_str(" %}");
+
+		if !optional_params.is_empty() {
+			render_call.push_str(&format!("\n{{% # optional: {} %}}", optional_params.join(", ")));
+		}
+
+		render_call
+	}
+}
+
+/// Inspect the parts of a [DocBlock] via [DocBlock::accept] by implementing only the methods you need
+pub trait DocBlockVisitor {
+	fn visit_description(&mut self, _description: &str) {}
+	fn visit_param(&mut self, _param: &Param) {}
+	fn visit_example(&mut self, _example: &str) {}
+}
+
+/// Build a [DocBlock] step by step without spelling out every field
+#[derive(Debug, Default)]
+pub struct DocBlockBuilder {
+	doc_block: DocBlock,
+}
+
+impl DocBlockBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the description of the doc block
+	pub fn description(mut self, description: impl Into<String>) -> Self {
+		self.doc_block.description = description.into();
+		self
+	}
+
+	/// Append a param
+	pub fn param(mut self, param: Param) -> Self {
+		self.doc_block = self.doc_block.with_param(param);
+		self
+	}
+
+	/// Append an example with the given body and an empty span
+	pub fn example(mut self, body: impl Into<String>) -> Self {
+		self.doc_block = self.doc_block.with_example(body);
+		self
+	}
+
+	pub fn build(self) -> DocBlock {
+		self.doc_block
+	}
+}
+
+/// Helper function to find variable paths within a single output tag expression while skipping string literals, filter
+/// names and named filter arguments
+fn collect_template_variables(expression: &str, variables: &mut Vec<String>) {
+	let bytes = expression.as_bytes();
+	let mut pos = 0;
+	let mut after_pipe = false;
+
+	while pos < bytes.len() {
+		match bytes[pos] {
+			quote @ (b'"' | b'\'') => {
+				pos = expression[pos + 1..].find(quote as char).map(|end| pos + end + 2).unwrap_or(bytes.len());
+			},
+			b'|' => {
+				after_pipe = true;
+				pos += 1;
+			},
+			b'a'..=b'z' | b'_'
+				if pos == 0 || !(bytes[pos - 1].is_ascii_alphanumeric() || b".]-".contains(&bytes[pos - 1])) =>
+			{
+				let len = expression[pos..]
+					.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+					.unwrap_or(expression.len() - pos);
+				let variable = expression[pos..pos + len].trim_end_matches('.');
+				let is_named_argument = expression[pos + len..].trim_start().starts_with(':');
+
+				if !after_pipe
+					&& !is_named_argument
+					&& !matches!(variable, "true" | "false" | "nil" | "blank" | "empty")
+					&& !variables.iter().any(|known| known == variable)
+				{
+					variables.push(String::from(variable));
+				}
+
+				after_pipe = false;
+				pos += len;
+			},
+			byte => {
+				if !byte.is_ascii_whitespace() {
+					after_pipe = false;
+				}
+				pos += 1;
+			},
+		}
+	}
 }
 
 /// The different types a parameter can be
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+///
+/// Serialized the way it is written in the doc tag e.g. `"string"`, `"product"` or `"number[][]"`, enums become
+/// `{ "type": "enum", "values": [...] }`, named objects `{ "type": "object", "name": "..." }` and constrained types
+/// `{ "type": "string", "constraints": { ... } }` with the array suffix on `type` e.g. `"enum[]"`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ParamType {
 	#[default]
 	String,
@@ -52,74 +537,1837 @@ pub enum ParamType {
 	Object,
 	ArrayOf(Box<ParamType>),
 	Shopify(String),
+	/// A string restricted to the given values e.g. `{enum: "left"|"right"}`
+	Enum(Vec<String>),
+	/// Accepts every primitive and Shopify object e.g. `{any}`
+	Any,
+	/// An object with the shape of a schema registered in [ParserConfig::named_object_schemas] e.g.
+	/// `{object: "article-card"}`
+	NamedObject(String),
+	/// A string or number with `key=value` constraints like its bounds e.g. `{string: min=1, max=100}`
+	Constrained {
+		base: ConstrainedBase,
+		constraints: BTreeMap<String, String>,
+	},
+}
+
+/// The types a [ParamType::Constrained] can restrict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstrainedBase {
+	String,
+	Number,
+}
+
+impl ConstrainedBase {
+	/// Look up the base by the name used in the doc tag e.g. `"string"`
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"string" => Some(ConstrainedBase::String),
+			"number" => Some(ConstrainedBase::Number),
+			_ => None,
+		}
+	}
+
+	/// The name used in the doc tag e.g. `"string"`
+	pub fn name(&self) -> &'static str {
+		match self {
+			ConstrainedBase::String => "string",
+			ConstrainedBase::Number => "number",
+		}
+	}
+}
+
+impl From<ConstrainedBase> for ParamType {
+	fn from(base: ConstrainedBase) -> Self {
+		match base {
+			ConstrainedBase::String => ParamType::String,
+			ConstrainedBase::Number => ParamType::Number,
+		}
+	}
+}
+
+// Tsify can't describe the custom serialization so we declare the type ourselves
+#[cfg(feature = "std")]
+#[wasm_bindgen(typescript_custom_section)]
+const PARAM_TYPE_DECLARATION: &str = r#"
+export type ParamType =
+	| string
+	| { type: string; values: string[] }
+	| { type: string; name: string }
+	| { type: string; constraints: Record<string, string> };
+"#;
+
+impl ParamType {
+	/// Number of `ArrayOf` wrappers around the innermost type e.g. `2` for `{string[][]}`
+	pub fn depth(&self) -> usize {
+		let mut depth = 0;
+		let mut param_type = self;
+		while let ParamType::ArrayOf(item_type) = param_type {
+			depth += 1;
+			param_type = item_type;
+		}
+		depth
+	}
+}
+
+impl Serialize for ParamType {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut base_type = self;
+		while let ParamType::ArrayOf(item_type) = base_type {
+			base_type = item_type;
+		}
+		let suffix = "[]".repeat(self.depth());
+
+		let name = match base_type {
+			ParamType::String => "string",
+			ParamType::Number => "number",
+			ParamType::Boolean => "boolean",
+			ParamType::Object => "object",
+			ParamType::Any => "any",
+			ParamType::Shopify(name) => name,
+			ParamType::Enum(values) => {
+				use serde::ser::SerializeStruct;
+
+				let mut state = serializer.serialize_struct("Enum", 2)?;
+				state.serialize_field("type", &format!("enum{suffix}"))?;
+				state.serialize_field("values", values)?;
+				return state.end();
+			},
+			ParamType::NamedObject(name) => {
+				use serde::ser::SerializeStruct;
+
+				let mut state = serializer.serialize_struct("NamedObject", 2)?;
+				state.serialize_field("type", &format!("object{suffix}"))?;
+				state.serialize_field("name", name)?;
+				return state.end();
+			},
+			ParamType::Constrained { base, constraints } => {
+				use serde::ser::SerializeStruct;
+
+				let mut state = serializer.serialize_struct("Constrained", 2)?;
+				state.serialize_field("type", &format!("{}{suffix}", base.name()))?;
+				state.serialize_field("constraints", constraints)?;
+				return state.end();
+			},
+			ParamType::ArrayOf(_) => unreachable!("all arrays were unwrapped above"),
+		};
+
+		serializer.serialize_str(&format!("{name}{suffix}"))
+	}
+}
+
+impl<'de> Deserialize<'de> for ParamType {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		use serde::de::Error;
+
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Repr {
+			Name(String),
+			Enum {
+				#[serde(rename = "type")]
+				type_: String,
+				values: Vec<String>,
+			},
+			NamedObject {
+				#[serde(rename = "type")]
+				type_: String,
+				name: String,
+			},
+			Constrained {
+				#[serde(rename = "type")]
+				type_: String,
+				constraints: BTreeMap<String, String>,
+			},
+		}
+
+		let (mut name, base_type) = match Repr::deserialize(deserializer)? {
+			Repr::Name(name) => (name, None),
+			Repr::Enum { type_, values } => (type_, Some(ParamType::Enum(values))),
+			Repr::NamedObject { type_, name } => (type_, Some(ParamType::NamedObject(name))),
+			Repr::Constrained { type_, constraints } => (
+				type_,
+				Some(ParamType::Constrained {
+					base: ConstrainedBase::String,
+					constraints,
+				}),
+			),
+		};
+
+		let mut depth = 0;
+		while let Some(item_type) = name.strip_suffix("[]") {
+			name.truncate(item_type.len());
+			depth += 1;
+		}
+
+		let base_type = match (base_type, name.as_str()) {
+			(Some(enum_type @ ParamType::Enum(_)), "enum") => enum_type,
+			(Some(named_object @ ParamType::NamedObject(_)), "object") => named_object,
+			(Some(ParamType::Enum(_)), other) => {
+				return Err(D::Error::custom(format!("expected type \"enum\" but found \"{other}\"")));
+			},
+			(Some(ParamType::Constrained { constraints, .. }), base_name) => match ConstrainedBase::from_name(base_name) {
+				Some(base) => ParamType::Constrained { base, constraints },
+				None => {
+					return Err(D::Error::custom(format!("expected type \"string\" or \"number\" but found \"{base_name}\"")));
+				},
+			},
+			(Some(_), other) => return Err(D::Error::custom(format!("expected type \"object\" but found \"{other}\""))),
+			(None, "") => return Err(D::Error::custom("expected a type name")),
+			(None, "string") => ParamType::String,
+			(None, "number") => ParamType::Number,
+			(None, "boolean") => ParamType::Boolean,
+			(None, "object") => ParamType::Object,
+			(None, "any") => ParamType::Any,
+			(None, _) => ParamType::Shopify(name),
+		};
+
+		Ok((0..depth).fold(base_type, |item_type, _| ParamType::ArrayOf(Box::new(item_type))))
+	}
+}
+
+/// The content of an `@deprecated` tag
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct DeprecatedInfo {
+	pub message: String,
+	/// Semver-like version the snippet was deprecated in e.g. `2.0.0`
+	pub version: Option<String>,
+	/// Name of the snippet to use instead when the message reads like `Use 'new-snippet' instead`
+	pub replacement: Option<String>,
+	/// Trailing URL of the message e.g. a changelog entry
+	pub link: Option<String>,
+}
+
+/// An external URL from an `@link` tag e.g. `@link https://example.com "Changelog"`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct DocLink {
+	pub url: String,
+	pub label: Option<String>,
+}
+
+/// A byte range within the parsed Liquid source, `end` is exclusive
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct Span {
+	pub start: usize,
+	pub end: usize,
+}
+
+/// The body of an @example tag and where the tag was found
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ExampleBlock {
+	pub body: String,
+	pub span: Span,
 }
 
 /// Type of param type within doc a tag
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct Param {
 	pub name: String,
 	pub description: Option<String>,
 	#[serde(rename = "type")]
 	pub type_: Option<ParamType>,
 	pub optional: bool,
+	pub span: Option<Span>,
 }
 
-/// Input type for [parse_files]
-#[derive(Debug, Serialize, Deserialize, Tsify)]
-#[tsify(into_wasm_abi, from_wasm_abi)]
-pub struct FileInput {
-	path: String,
-	content: String,
+impl Param {
+	/// Positional params are documented by their index e.g. `@param {string} 0 - First argument`
+	pub fn is_positional(&self) -> bool {
+		self.name.parse::<usize>().is_ok()
+	}
+
+	/// The innermost type of this param with all `ArrayOf` wrappers removed e.g. `String` for `{string[][]}`
+	pub fn base_type(&self) -> Option<&ParamType> {
+		let mut base_type = self.type_.as_ref()?;
+		while let ParamType::ArrayOf(item_type) = base_type {
+			base_type = item_type;
+		}
+		Some(base_type)
+	}
+
+	/// Check if this param is an array of any depth
+	pub fn is_array(&self) -> bool {
+		matches!(self.type_, Some(ParamType::ArrayOf(_)))
+	}
+
+	/// Set the name of this param
+	pub fn with_name(mut self, name: impl Into<String>) -> Self {
+		self.name = name.into();
+		self
+	}
+
+	/// Set the type of this param
+	pub fn with_type(mut self, type_: ParamType) -> Self {
+		self.type_ = Some(type_);
+		self
+	}
+
+	/// Set the description of this param
+	pub fn with_description(mut self, description: impl Into<String>) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	/// Mark this param as optional like `[name]` does
+	pub fn as_optional(mut self) -> Self {
+		self.optional = true;
+		self
+	}
 }
 
-/// Helper function to parse content of a file
-fn parse_content(input: &str) -> ParseResult {
-	let mut result = ParseResult {
-		success: Vec::new(),
-		errors: Vec::new(),
-	};
+/// Borrowed version of [Param] that points into the parsed content where possible instead of allocating, see
+/// [LiquidDocs::parse_param_ref]
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+pub struct ParamRef<'a> {
+	pub name: Cow<'a, str>,
+	pub description: Option<Cow<'a, str>>,
+	#[serde(rename = "type")]
+	pub type_: Option<ParamType>,
+	pub optional: bool,
+	pub span: Option<Span>,
+}
 
-	if let Some(blocks) = LiquidDocs::extract_doc_blocks(input) {
-		for block in blocks {
-			match LiquidDocs::parse_doc_content(block) {
-				Ok(block_type) => result.success.push(block_type),
-				Err(error) => {
-					result.errors.push(ParseError {
-						line: error.get_line(),
-						column: error.get_column(),
-						message: error.to_string(),
-					});
-				},
-			}
+impl ParamRef<'_> {
+	/// Copy all borrowed strings into an owned [Param]
+	pub fn into_owned(self) -> Param {
+		Param {
+			name: self.name.into_owned(),
+			description: self.description.map(Cow::into_owned),
+			type_: self.type_,
+			optional: self.optional,
+			span: self.span,
 		}
 	}
+}
 
-	result
+impl<'a> From<&'a Param> for ParamRef<'a> {
+	fn from(param: &'a Param) -> Self {
+		ParamRef {
+			name: Cow::Borrowed(&param.name),
+			description: param.description.as_deref().map(Cow::Borrowed),
+			type_: param.type_.clone(),
+			optional: param.optional,
+			span: param.span,
+		}
+	}
 }
 
-/// Parse a Vec<FileInput> and return Vec<LiquidFile>
-#[wasm_bindgen]
-pub fn parse_batch(files: Vec<FileInput>) -> Vec<LiquidFile> {
-	let mut all_files = Vec::with_capacity(files.len());
+/// A named cluster of params started by `@param-group "Name"`, its params are also part of [DocBlock::param]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ParamGroup {
+	pub name: String,
+	pub params: Vec<Param>,
+}
 
-	for file in files {
-		let parse_result = parse_content(&file.content);
-		all_files.push(LiquidFile {
-			path: file.path,
-			liquid_types: if parse_result.success.is_empty() && parse_result.errors.is_empty() {
-				None
-			} else {
-				Some(parse_result)
+/// The outcome of [check_backward_compat]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct CompatResult {
+	pub is_compatible: bool,
+	pub breaking_changes: Vec<BreakingChange>,
+}
+
+/// A change between two versions of a doc block that breaks existing `{% render %}` calls
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum BreakingChange {
+	RequiredParamRemoved {
+		name: String,
+	},
+	RequiredParamAdded {
+		name: String,
+	},
+	OptionalParamMadeRequired {
+		name: String,
+	},
+	ParamTypeChanged {
+		name: String,
+		old_type: Option<ParamType>,
+		new_type: Option<ParamType>,
+	},
+}
+
+impl core::fmt::Display for BreakingChange {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self {
+			BreakingChange::RequiredParamRemoved { name } => write!(f, "Required param \"{name}\" was removed"),
+			BreakingChange::RequiredParamAdded { name } => write!(f, "Required param \"{name}\" was added"),
+			BreakingChange::OptionalParamMadeRequired { name } => write!(f, "Optional param \"{name}\" is now required"),
+			BreakingChange::ParamTypeChanged { name, .. } => {
+				write!(f, "Type of param \"{name}\" changed in a way that rejects previously valid values")
 			},
-		});
+		}
 	}
-
-	all_files
 }
 
-/// Parse a string of Liquid code and return Vec<DocBlock>
-#[wasm_bindgen]
-pub fn parse(input: String) -> ParseResult {
-	parse_content(&input)
+/// Compare the doc block of a previous release with the current one and list the changes that break existing callers
+pub fn check_backward_compat(old: &DocBlock, new: &DocBlock) -> CompatResult {
+	let mut breaking_changes = Vec::new();
+
+	for old_param in &old.param {
+		match new.param.iter().find(|param| param.name == old_param.name) {
+			None if !old_param.optional => breaking_changes.push(BreakingChange::RequiredParamRemoved {
+				name: old_param.name.clone(),
+			}),
+			None => {},
+			Some(new_param) => {
+				if old_param.optional && !new_param.optional {
+					breaking_changes.push(BreakingChange::OptionalParamMadeRequired {
+						name: old_param.name.clone(),
+					});
+				}
+				if !is_type_compatible(old_param.type_.as_ref(), new_param.type_.as_ref()) {
+					breaking_changes.push(BreakingChange::ParamTypeChanged {
+						name: old_param.name.clone(),
+						old_type: old_param.type_.clone(),
+						new_type: new_param.type_.clone(),
+					});
+				}
+			},
+		}
+	}
+
+	// existing calls don't pass params that didn't exist yet
+	for new_param in &new.param {
+		if !new_param.optional && !old.param.iter().any(|param| param.name == new_param.name) {
+			breaking_changes.push(BreakingChange::RequiredParamAdded {
+				name: new_param.name.clone(),
+			});
+		}
+	}
+
+	CompatResult {
+		is_compatible: breaking_changes.is_empty(),
+		breaking_changes,
+	}
+}
+
+/// Helper function to check if every value the old type accepted is still accepted by the new type
+fn is_type_compatible(old_type: Option<&ParamType>, new_type: Option<&ParamType>) -> bool {
+	match (old_type, new_type) {
+		// an untyped or `any` param accepts anything
+		(_, None | Some(ParamType::Any)) => true,
+		(None, Some(_)) => false,
+		(Some(ParamType::ArrayOf(old_item)), Some(ParamType::ArrayOf(new_item))) => {
+			is_type_compatible(Some(old_item), Some(new_item))
+		},
+		// widening an enum keeps all previous values valid
+		(Some(ParamType::Enum(old_values)), Some(ParamType::Enum(new_values))) => {
+			old_values.iter().all(|value| new_values.contains(value))
+		},
+		(Some(ParamType::Enum(_)), Some(ParamType::String)) => true,
+		(Some(ParamType::Shopify(_) | ParamType::NamedObject(_)), Some(ParamType::Object)) => true,
+		// dropping the constraints only widens the accepted values
+		(Some(ParamType::Constrained { base, .. }), Some(new_type)) if ParamType::from(*base) == *new_type => true,
+		(Some(old_type), Some(new_type)) => old_type == new_type,
+	}
+}
+
+/// Options to tweak how [parse_with_config] treats its input
+#[derive(Debug, Clone)]
+pub struct ParserConfig {
+	/// Doc blocks larger than this many bytes are reported as an error and skipped instead of parsed
+	pub max_doc_block_bytes: Option<usize>,
+	/// Record how long each file took to parse in [LiquidFile::parse_duration_ns], needs the `std` feature
+	pub measure_timing: bool,
+	/// Types nested deeper than this many arrays e.g. `{string[][][]}` are reported as an error
+	pub max_array_depth: usize,
+	/// Doc blocks with more params than this are reported as an error
+	pub max_params: Option<usize>,
+	/// Warn about examples with unbalanced `{% %}` or `{{ }}` tags
+	pub validate_example_syntax: bool,
+	/// How many columns a tab counts as when stripping the common indentation of an example
+	pub tab_width: usize,
+	/// Whether the column of an error counts bytes or characters
+	pub column_mode: ColumnMode,
+	/// Warn about params named like a built-in Liquid object e.g. `product` or `cart` which they hide inside the snippet
+	pub warn_on_shadowing: bool,
+	/// Names of custom object shapes params can refer to with `{object: "name"}`
+	pub named_object_schemas: Vec<String>,
+}
+
+impl Default for ParserConfig {
+	fn default() -> Self {
+		ParserConfig {
+			// Doc blocks are written by hand so we trust their size unless a server-side caller opts into a limit
+			max_doc_block_bytes: None,
+			// Measuring costs a clock read per file which nobody needs outside of profiling
+			measure_timing: false,
+			// Real themes rarely go past two levels, five leaves headroom while still stopping crafted input early
+			max_array_depth: 5,
+			// How many params a snippet should take is a style decision so there is no limit unless a team opts into one
+			max_params: None,
+			// Examples are illustrative and often abbreviated so checking them is opt-in
+			validate_example_syntax: false,
+			// Most editors default to four columns and Shopify's own themes are indented that way
+			tab_width: 4,
+			// Bytes are what the parser has always reported and what Rust string slicing expects
+			column_mode: ColumnMode::Bytes,
+			// A param hiding a built-in object is an easy mistake to make and a confusing one to debug
+			warn_on_shadowing: true,
+			// Schemas are project specific so none are known until the caller registers them
+			named_object_schemas: Vec::new(),
+		}
+	}
+}
+
+/// How the column of a position is counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnMode {
+	/// Count UTF-8 bytes, `🎉` is four columns wide
+	#[default]
+	Bytes,
+	/// Count Unicode characters, `🎉` is a single column
+	Characters,
+}
+
+/// Input type for [parse_files]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "std", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct FileInput {
+	path: String,
+	content: String,
+}
+
+impl FileInput {
+	pub fn new(path: impl Into<String>, content: impl Into<String>) -> Self {
+		FileInput {
+			path: path.into(),
+			content: content.into(),
+		}
+	}
+
+	/// Read the file at `path` into a FileInput that uses the lossy string form of `path` as its path
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	pub fn from_path(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let path = path.as_ref();
+		Ok(FileInput::new(path.to_string_lossy(), std::fs::read_to_string(path)?))
+	}
+}
+
+/// The error type our WASM bindings throw when they can't process their input
+#[cfg(feature = "std")]
+#[derive(Debug, Serialize, Deserialize, Tsify)]
+pub struct WasmError {
+	pub message: String,
+	pub code: String,
+}
+
+#[cfg(feature = "std")]
+impl From<serde_wasm_bindgen::Error> for WasmError {
+	fn from(error: serde_wasm_bindgen::Error) -> Self {
+		WasmError {
+			message: error.to_string(),
+			code: String::from("InvalidInput"),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<WasmError> for JsValue {
+	fn from(error: WasmError) -> Self {
+		serde_wasm_bindgen::to_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.message))
+	}
+}
+
+/// Helper function to convert a value for JavaScript with maps as plain objects, matching what tsify returns for our
+/// types so e.g. `constraints.min` can be read directly
+#[cfg(feature = "std")]
+fn to_js_value<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+	value.serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+}
+
+/// Helper function to run a closure and return its result with the wall-clock nanoseconds it took
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+	let start = std::time::Instant::now();
+	let result = f();
+	(result, Some(start.elapsed().as_nanos() as u64))
+}
+
+/// Helper function to run a closure and return its result with the wall-clock nanoseconds it took
+/// `std::time::Instant` panics on wasm32-unknown-unknown so we fall back to the millisecond precision of `Date.now()`
+#[cfg(all(feature = "std", target_arch = "wasm32"))]
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+	let start = js_sys::Date::now();
+	let result = f();
+	(result, Some(((js_sys::Date::now() - start) * 1_000_000.0) as u64))
+}
+
+/// Helper function for `no_std` builds which have no clock so the duration is always `None`
+#[cfg(not(feature = "std"))]
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+	(f(), None)
+}
+
+/// Helper function to parse a single file into a LiquidFile
+fn parse_file(file: FileInput, config: &ParserConfig) -> LiquidFile {
+	let (parse_result, parse_duration_ns) = if config.measure_timing {
+		measure(|| LiquidDocs::parse_with_config(&file.content, config))
+	} else {
+		(LiquidDocs::parse_with_config(&file.content, config), None)
+	};
+
+	LiquidFile {
+		path: file.path,
+		liquid_types: if parse_result.success.is_empty() && parse_result.errors.is_empty() {
+			None
+		} else {
+			Some(parse_result)
+		},
+		parse_duration_ns,
+	}
+}
+
+/// Helper function to read the FileInputs of a WASM batch, each paired with an `InvalidUtf8` [ParseResult] when its
+/// content contained lone UTF-16 surrogates
+#[cfg(feature = "std")]
+fn parse_js_files(input: JsValue) -> Result<Vec<(FileInput, Option<ParseResult>)>, WasmError> {
+	let files: Vec<FileInput> = serde_wasm_bindgen::from_value(input.clone())?;
+	// the content has already been converted lossily so we check the JavaScript strings it came from
+	let inputs = input.dyn_ref::<js_sys::Array>();
+
+	Ok(
+		files
+			.into_iter()
+			.enumerate()
+			.map(|(index, file)| {
+				let encoding_error = inputs
+					.and_then(|inputs| js_sys::Reflect::get(&inputs.get(index as u32), &JsValue::from_str("content")).ok())
+					.and_then(|content| content.dyn_into::<js_sys::JsString>().ok())
+					.and_then(|content| check_utf16(&content).err());
+				(file, encoding_error)
+			})
+			.collect(),
+	)
+}
+
+/// Helper function to parse a file read by [parse_js_files] unless its content was already rejected
+#[cfg(feature = "std")]
+fn parse_js_file((file, encoding_error): (FileInput, Option<ParseResult>), config: &ParserConfig) -> LiquidFile {
+	match encoding_error {
+		Some(parse_result) => LiquidFile {
+			path: file.path,
+			liquid_types: Some(parse_result),
+			parse_duration_ns: None,
+		},
+		None => parse_file(file, config),
+	}
+}
+
+/// Parse a Vec<FileInput> and return Vec<LiquidFile>. The output at index `i` always belongs to the input at index `i`.
+/// Malformed input throws a [WasmError], use [parse_batch_with_config] from Rust.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn parse_batch(
+	#[wasm_bindgen(unchecked_param_type = "FileInput[]")] input: JsValue,
+) -> Result<Vec<LiquidFile>, JsValue> {
+	let config = ParserConfig::default();
+	Ok(parse_js_files(input)?.into_iter().map(|file| parse_js_file(file, &config)).collect())
+}
+
+/// Parse a Vec<FileInput> and return Vec<LiquidFile> while calling `on_progress(processed_count, total_count)` after
+/// each file. The callback is invoked synchronously and must not throw, an exception aborts the batch and is rethrown.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn parse_batch_with_progress(input: JsValue, on_progress: js_sys::Function) -> Result<JsValue, JsValue> {
+	let files = parse_js_files(input)?;
+	let total_count = files.len();
+	let mut all_files = Vec::with_capacity(total_count);
+
+	for (index, file) in files.into_iter().enumerate() {
+		all_files.push(parse_js_file(file, &ParserConfig::default()));
+		on_progress.call2(&JsValue::NULL, &JsValue::from(index + 1), &JsValue::from(total_count))?;
+	}
+
+	Ok(to_js_value(&all_files).map_err(WasmError::from)?)
+}
+
+/// Parse a Vec<FileInput> and return a ReadableStream that emits each LiquidFile as soon as it has been parsed
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn parse_batch_streaming(input: JsValue) -> Result<JsValue, JsValue> {
+	let mut files = parse_js_files(input)?.into_iter();
+
+	// Files are only parsed when the consumer pulls the next chunk so we never hold more than one result at a time
+	let pull = Closure::<dyn FnMut(ReadableStreamDefaultController) -> Result<(), JsValue>>::new(
+		move |controller: ReadableStreamDefaultController| match files.next() {
+			Some(file) => {
+				let chunk = to_js_value(&parse_js_file(file, &ParserConfig::default())).map_err(WasmError::from)?;
+				controller.enqueue_with_chunk(&chunk)
+			},
+			None => controller.close(),
+		},
+	);
+
+	let underlying_source = js_sys::Object::new();
+	js_sys::Reflect::set(&underlying_source, &JsValue::from_str("pull"), &pull.into_js_value())?;
+
+	Ok(ReadableStream::new_with_underlying_source(&underlying_source)?.into())
+}
+
+/// Aggregate the [BatchStats] of a Vec<LiquidFile> returned by [parse_batch]
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn compute_stats(input: JsValue) -> Result<JsValue, JsValue> {
+	let files: Vec<LiquidFile> = serde_wasm_bindgen::from_value(input).map_err(WasmError::from)?;
+	Ok(to_js_value(&compute_batch_stats(&files)).map_err(WasmError::from)?)
+}
+
+/// All Shopify object names that are accepted as param types e.g. to autocomplete `@param {…}` in an editor
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn get_allowed_shopify_objects() -> JsValue {
+	shopify_liquid_objects::SHOPIFY_ALLOWED_OBJECTS
+		.iter()
+		.chain(liquid_docs::SETTINGS_OBJECTS.iter())
+		.map(|name| JsValue::from_str(name))
+		.collect::<js_sys::Array>()
+		.into()
+}
+
+/// The Shopify documentation page of a Shopify object param type e.g. to link `{product}` in generated docs
+pub fn to_shopify_docs_url(name: &str) -> Option<String> {
+	shopify_liquid_objects::SHOPIFY_ALLOWED_OBJECTS
+		.contains(&name)
+		.then(|| format!("https://shopify.dev/docs/api/liquid/objects/{name}"))
+}
+
+/// The version of this crate e.g. to log which parser produced a result
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn version() -> String {
+	String::from(env!("CARGO_PKG_VERSION"))
+}
+
+/// Parse a string of Liquid code and return Vec<DocBlock>. Anything but a string throws a [WasmError], use
+/// [LiquidDocs::parse] from Rust.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn parse(#[wasm_bindgen(unchecked_param_type = "string")] input: JsValue) -> Result<ParseResult, JsValue> {
+	let input = input.dyn_into::<js_sys::JsString>().map_err(|_| WasmError {
+		message: String::from("Expected the Liquid code as a string"),
+		code: String::from("InvalidInput"),
+	})?;
+
+	match check_utf16(&input) {
+		Ok(()) => Ok(LiquidDocs::parse(&String::from(input))),
+		Err(parse_result) => Ok(parse_result),
+	}
+}
+
+/// Helper function to reject a JavaScript string with lone UTF-16 surrogates before it is converted to UTF-8 which would
+/// silently swap each of them for U+FFFD
+#[cfg(feature = "std")]
+fn check_utf16(input: &js_sys::JsString) -> Result<(), ParseResult> {
+	if input.is_valid_utf16() {
+		return Ok(());
+	}
+
+	let (line, column) = find_lone_surrogate(input.iter()).unwrap_or((1, 1));
+	Err(ParseResult {
+		success: Vec::new(),
+		errors: alloc::vec![ParseError::from(ParsingError::InvalidUtf8 { line, column })],
+		warnings: Vec::new(),
+	})
+}
+
+/// Helper function to find the line and column of the first lone surrogate in UTF-16 code units, the column counts the
+/// bytes of the valid UTF-8 before it like every other column we report
+#[cfg(any(feature = "std", test))]
+fn find_lone_surrogate(code_units: impl IntoIterator<Item = u16>) -> Option<(usize, usize)> {
+	let mut valid_prefix = String::new();
+
+	for ch in char::decode_utf16(code_units) {
+		match ch {
+			Ok(ch) => valid_prefix.push(ch),
+			Err(_) => return Some(line_and_column(&valid_prefix, valid_prefix.len())),
+		}
+	}
+
+	None
+}
+
+/// Parse a string of Liquid code with a custom [ParserConfig]
+pub fn parse_with_config(input: &str, config: &ParserConfig) -> ParseResult {
+	LiquidDocs::parse_with_config(input, config)
+}
+
+/// Get the line and column (1 indexed) of a byte offset within Liquid source the same way the parser reports them, the
+/// column counts bytes
+pub fn line_and_column(content: &str, byte_offset: usize) -> (usize, usize) {
+	let bytes = content.as_bytes();
+	let mut line = 1;
+	let mut last_newline_pos = 0;
+
+	for (i, byte) in bytes.iter().enumerate().take(byte_offset.min(bytes.len())) {
+		if *byte == b'\n' {
+			line += 1;
+			last_newline_pos = i + 1;
+		}
+	}
+
+	let column = byte_offset - last_newline_pos + 1;
+	(line, column)
+}
+
+/// The glob pattern that matches every liquid file below a root directory
+pub const DEFAULT_LIQUID_GLOB: &str = "**/*.liquid";
+
+/// Find all files matching `glob_pattern` below `root` and read them into FileInputs ready for [parse_batch_with_config]
+/// Pass [DEFAULT_LIQUID_GLOB] to pick up every liquid file of a theme.
+#[cfg(all(feature = "glob", not(target_arch = "wasm32")))]
+pub fn discover_liquid_files(
+	root: impl AsRef<std::path::Path>,
+	glob_pattern: &str,
+) -> Result<Vec<FileInput>, std::io::Error> {
+	// The root is escaped so directory names like `[locale]` aren't treated as part of the pattern
+	let pattern = std::path::Path::new(&glob::Pattern::escape(&root.as_ref().to_string_lossy())).join(glob_pattern);
+	let paths = glob::glob(&pattern.to_string_lossy())
+		.map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+
+	let mut files = Vec::new();
+	for path in paths {
+		let path = path.map_err(std::io::Error::from)?;
+		if path.is_file() {
+			files.push(FileInput::from_path(&path)?);
+		}
+	}
+
+	Ok(files)
+}
+
+/// Parse a Vec<FileInput> with a custom [ParserConfig]. The output at index `i` always belongs to the input at index `i`.
+pub fn parse_batch_with_config(files: Vec<FileInput>, config: &ParserConfig) -> Vec<LiquidFile> {
+	let mut all_files = Vec::with_capacity(files.len());
+
+	for file in files {
+		all_files.push(parse_file(file, config));
+	}
+
+	all_files
+}
+
+/// Parse a Vec<FileInput> into a map from each path to its [ParseResult] for lookups by path. Every input path is a key,
+/// files without a doc tag map to an empty [ParseResult] and later duplicates of a path replace earlier ones.
+#[cfg(feature = "std")]
+pub fn parse_batch_map(files: Vec<FileInput>) -> std::collections::HashMap<String, ParseResult> {
+	let config = ParserConfig::default();
+
+	files
+		.into_iter()
+		.map(|file| {
+			let file = parse_file(file, &config);
+			let parse_result = file.liquid_types.unwrap_or(ParseResult {
+				success: Vec::new(),
+				errors: Vec::new(),
+				warnings: Vec::new(),
+			});
+			(file.path, parse_result)
+		})
+		.collect()
+}
+
+/// Update a previous [parse_batch] result in watch mode by only parsing the `changed` files and dropping the `deleted`
+/// paths. Unchanged files keep their position, changed files replace their previous entry and new files are appended.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn parse_batch_update(previous: Vec<LiquidFile>, changed: Vec<FileInput>, deleted: Vec<String>) -> Vec<LiquidFile> {
+	let config = ParserConfig::default();
+	let mut all_files = previous;
+	all_files.retain(|file| !deleted.contains(&file.path));
+
+	for file in changed {
+		let parsed_file = parse_file(file, &config);
+		match all_files.iter_mut().find(|file| file.path == parsed_file.path) {
+			Some(previous_file) => *previous_file = parsed_file,
+			None => all_files.push(parsed_file),
+		}
+	}
+
+	all_files
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	#[test]
+	fn positional_params_test() {
+		let result = LiquidDocs::parse("{% doc %}@param {string} 0 - First\n@param {number} 1 - Second{% enddoc %}");
+		assert!(result.success[0].param.iter().all(Param::is_positional));
+		assert!(result.warnings.is_empty());
+
+		let result = LiquidDocs::parse("{% doc %}@param {string} 0 - First\n@param foo - Named{% enddoc %}");
+		assert!(result.success[0].param[0].is_positional());
+		assert!(!result.success[0].param[1].is_positional());
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].message, "Positional and named params are mixed in the same doc block on line 2");
+		assert_eq!(result.warnings[0].line, 2);
+
+		let result = LiquidDocs::parse(
+			"{% doc %}@param foo
+@param bar
+@param 0
+@param 1
+@param baz{% enddoc %}",
+		);
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].line, 3);
+	}
+
+	#[test]
+	fn description_first_line_test() {
+		let doc_block = DocBlock::with_description("Renders a card\nwith an optional image");
+		assert_eq!(doc_block.description_first_line(), "Renders a card");
+		assert_eq!(DocBlock::with_description("Renders a card").description_first_line(), "Renders a card");
+		assert_eq!(DocBlock::default().description_first_line(), "");
+	}
+
+	#[test]
+	fn contains_param_test() {
+		let doc_block = LiquidDocs::parse_doc_content("@param {string} title\n@param [count]").unwrap();
+		assert!(doc_block.contains_param("title"));
+		assert!(doc_block.contains_param("count"));
+		assert!(!doc_block.contains_param("Title"));
+		assert_eq!(doc_block.param_count(), 2);
+		assert_eq!(DocBlock::default().param_count(), 0);
+	}
+
+	#[test]
+	fn example_bodies_test() {
+		let doc_block = LiquidDocs::parse_doc_content("@example\n{% render 'a' %}\n@example\n{% render 'b' %}").unwrap();
+		assert_eq!(doc_block.example_bodies().collect::<Vec<_>>(), vec!["{% render 'a' %}", "{% render 'b' %}"]);
+		assert_eq!(DocBlock::default().example_bodies().count(), 0);
+		assert_eq!(doc_block.first_example(), Some("{% render 'a' %}"));
+		assert!(doc_block.has_examples());
+
+		let doc_block = DocBlockBuilder::new().description("No examples").build();
+		assert_eq!(doc_block.first_example(), None);
+		assert!(!doc_block.has_examples());
+	}
+
+	#[test]
+	fn example_unwrapped_raw_test() {
+		let doc_block = DocBlock::default()
+			.with_example("{% raw %}\n  {% render 'card' %}\n    {{ title }}\n{% endraw %}")
+			.with_example("{%- raw -%}{{ title }}{%- endraw -%}")
+			.with_example("{% raw %}{{ a }}{% endraw %}\n{{ b }}")
+			.with_example("{% raw %}{{ a }}{% endraw %}{% raw %}{{ b }}{% endraw %}")
+			.with_example("{% render 'card' %}");
+
+		assert_eq!(
+			doc_block.example_unwrapped_raw(),
+			vec![
+				"{% render 'card' %}\n  {{ title }}",
+				"{{ title }}",
+				"{% raw %}{{ a }}{% endraw %}\n{{ b }}",
+				"{% raw %}{{ a }}{% endraw %}{% raw %}{{ b }}{% endraw %}",
+				"{% render 'card' %}",
+			]
+		);
+	}
+
+	#[test]
+	fn base_type_test() {
+		let doc_block = LiquidDocs::parse_doc_content("@param {string[][]} a\n@param {number} b\n@param c").unwrap();
+
+		assert_eq!(doc_block.param[0].base_type(), Some(&ParamType::String));
+		assert!(doc_block.param[0].is_array());
+		assert_eq!(doc_block.param[1].base_type(), Some(&ParamType::Number));
+		assert!(!doc_block.param[1].is_array());
+		assert_eq!(doc_block.param[2].base_type(), None);
+		assert!(!doc_block.param[2].is_array());
+	}
+
+	#[test]
+	fn param_builder_test() {
+		let param = Param::default()
+			.with_name("loading")
+			.with_type(ParamType::String)
+			.with_description("The html loading attribute")
+			.as_optional();
+
+		assert_eq!(
+			param,
+			Param {
+				name: String::from("loading"),
+				description: Some(String::from("The html loading attribute")),
+				type_: Some(ParamType::String),
+				optional: true,
+				span: None,
+			}
+		);
+		assert!(!Param::default().with_name("alt").optional);
+	}
+
+	#[test]
+	fn doc_block_builder_test() {
+		let doc_block = DocBlockBuilder::new()
+			.description("Renders an image block.")
+			.param(Param::default().with_name("alt").with_type(ParamType::String))
+			.param(Param::default().with_name("loading").as_optional())
+			.example("{% render 'image', alt: 'Cat' %}")
+			.build();
+
+		assert_eq!(doc_block.description, "Renders an image block.");
+		assert_eq!(doc_block.param_count(), 2);
+		assert!(doc_block.param[1].optional);
+		assert_eq!(doc_block.example_bodies().collect::<Vec<_>>(), vec!["{% render 'image', alt: 'Cat' %}"]);
+		assert_eq!(doc_block.example[0].span, Span::default());
+		assert_eq!(DocBlockBuilder::new().build(), DocBlock::default());
+	}
+
+	#[test]
+	fn doc_block_with_test() {
+		let doc_block = DocBlock::with_description("My snippet")
+			.with_param(Param::default().with_name("title"))
+			.with_example("{% render 'my-snippet', title: 'Hi' %}");
+
+		assert_eq!(
+			doc_block,
+			DocBlockBuilder::new()
+				.description("My snippet")
+				.param(Param::default().with_name("title"))
+				.example("{% render 'my-snippet', title: 'Hi' %}")
+				.build()
+		);
+		assert_eq!(DocBlock::with_description("").param_count(), 0);
+	}
+
+	#[test]
+	fn param_ref_test() {
+		let param =
+			Param::default().with_name("title").with_type(ParamType::String).with_description("The title").as_optional();
+		let param_ref = ParamRef::from(&param);
+
+		assert!(matches!(param_ref.name, Cow::Borrowed("title")));
+		assert_eq!(serde_json::to_string(&param_ref).unwrap(), serde_json::to_string(&param).unwrap());
+		assert_eq!(param_ref.into_owned(), param);
+	}
+
+	#[test]
+	fn param_type_depth_test() {
+		assert_eq!(ParamType::String.depth(), 0);
+		assert_eq!(ParamType::Shopify(String::from("product")).depth(), 0);
+		assert_eq!(ParamType::ArrayOf(Box::new(ParamType::Number)).depth(), 1);
+		assert_eq!(ParamType::ArrayOf(Box::new(ParamType::ArrayOf(Box::new(ParamType::String)))).depth(), 2);
+
+		let deep_type = (0..1_000).fold(ParamType::Boolean, |item_type, _| ParamType::ArrayOf(Box::new(item_type)));
+		assert_eq!(deep_type.depth(), 1_000);
+	}
+
+	#[test]
+	fn doc_block_visitor_test() {
+		#[derive(Default)]
+		struct Collector {
+			visited: Vec<String>,
+		}
+
+		impl DocBlockVisitor for Collector {
+			fn visit_description(&mut self, description: &str) {
+				self.visited.push(format!("description: {description}"));
+			}
+
+			fn visit_param(&mut self, param: &Param) {
+				self.visited.push(format!("param: {}", param.name));
+			}
+		}
+
+		let doc_block = LiquidDocs::parse_doc_content("Card\n@param title\n@param [count]\n@example\nfoo").unwrap();
+		let mut collector = Collector::default();
+		doc_block.accept(&mut collector);
+		assert_eq!(collector.visited, vec!["description: Card", "param: title", "param: count"]);
+	}
+
+	#[test]
+	fn map_and_filter_params_test() {
+		let doc_block = LiquidDocs::parse_doc_content(
+			"Card\n@param title - The title\n@param-group \"Layout\"\n@param [gap]\n@example\nfoo",
+		)
+		.unwrap();
+
+		let mapped = doc_block.map_params(|mut param| {
+			param.name = param.name.to_uppercase();
+			param
+		});
+		assert_eq!(mapped.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["TITLE", "GAP"]);
+		assert_eq!(mapped.param_groups[0].params[0].name, "GAP");
+		assert_eq!(mapped.description, "Card");
+		assert_eq!(mapped.example_bodies().collect::<Vec<_>>(), vec!["foo"]);
+
+		let filtered = mapped.filter_params(|param| !param.optional);
+		assert_eq!(filtered.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["TITLE"]);
+		assert!(filtered.param_groups[0].params.is_empty());
+		assert_eq!(filtered.example.len(), 1);
+	}
+
+	#[test]
+	fn union_and_intersection_test() {
+		let old = LiquidDocs::parse_doc_content("Old card\n@param {string} title\n@param [count]").unwrap();
+		let new = LiquidDocs::parse_doc_content("New card\n@param {number} title\n@param image").unwrap();
+
+		let union = old.union(&new);
+		assert_eq!(union.description, "Old card");
+		assert_eq!(
+			union.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(),
+			vec!["title", "count", "image"]
+		);
+		assert_eq!(union.param[0].type_, Some(ParamType::String));
+
+		let grouped = LiquidDocs::parse_doc_content(
+			"@param-group \"Content\"\n@param title\n@link https://example.com/card\n@link https://example.com/old",
+		)
+		.unwrap();
+		let other_grouped = LiquidDocs::parse_doc_content(
+			"@param-group \"Content\"\n@param title\n@param body\n@param-group \"Layout\"\n@param width\n@link https://example.com/card \"Card\"\n@link https://example.com/new",
+		)
+		.unwrap();
+		let grouped_union = grouped.union(&other_grouped);
+		assert_eq!(
+			grouped_union
+				.param_groups
+				.iter()
+				.map(|group| (group.name.as_str(), group.params.iter().map(|param| param.name.as_str()).collect::<Vec<_>>()))
+				.collect::<Vec<_>>(),
+			vec![("Content", vec!["title", "body"]), ("Layout", vec!["width"])]
+		);
+		assert_eq!(
+			grouped_union.links.iter().map(|link| (link.url.as_str(), link.label.as_deref())).collect::<Vec<_>>(),
+			vec![
+				("https://example.com/card", None),
+				("https://example.com/old", None),
+				("https://example.com/new", None)
+			]
+		);
+
+		let intersection = old.intersection(&new);
+		assert_eq!(intersection.description, "Old card");
+		assert_eq!(intersection.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["title"]);
+		assert_eq!(intersection.param[0].type_, Some(ParamType::String));
+
+		assert!(old.intersection(&DocBlock::default()).param.is_empty());
+		assert_eq!(DocBlock::default().union(&new).param.len(), 2);
+	}
+
+	#[test]
+	fn template_variables_test() {
+		let doc_block = DocBlock {
+			description: String::new(),
+			deprecated: None,
+			param: Vec::new(),
+			param_groups: Vec::new(),
+			links: Vec::new(),
+			example: [
+				"{% render 'price', price: variant.price %}\n{{ variant.price | money }}",
+				"{{ settings.color | default: 'red' }} {{ 'text' }} {{ product.title | escape }}",
+				"{{- image | image_url: width: 300 | image_tag: alt: product.title -}} {{ true }}",
+				"{{ product.images[0].src }} {{ unclosed",
+			]
+			.into_iter()
+			.map(|body| ExampleBlock {
+				body: String::from(body),
+				span: Span::default(),
+			})
+			.collect(),
+		};
+
+		assert_eq!(
+			doc_block.template_variables(),
+			vec![
+				String::from("variant.price"),
+				String::from("settings.color"),
+				String::from("product.title"),
+				String::from("image"),
+				String::from("product.images"),
+				String::from("unclosed"),
+			]
+		);
+		assert!(DocBlock::default().template_variables().is_empty());
+	}
+
+	#[test]
+	fn to_render_call_test() {
+		let doc_block = LiquidDocs::parse_doc_content(
+			"@param {string} title\n@param {number} [count]\n@param {boolean} open\n@param {product} product\n@param [foo]",
+		)
+		.unwrap();
+
+		assert_eq!(
+			doc_block.to_render_call("card"),
+			"{% render 'card', title: '', open: false, product: nil %}\n{% # optional: count: 0, foo: nil %}"
+		);
+		assert_eq!(DocBlock::default().to_render_call("card"), "{% render 'card' %}");
+	}
+
+	#[test]
+	fn parse_batch_keeps_input_order_test() {
+		let mut files = (0..50)
+			.map(|index| FileInput {
+				path: format!("snippets/file-{index}.liquid"),
+				content: format!("{{% doc %}}File {index}{{% enddoc %}}"),
+			})
+			.collect::<Vec<_>>();
+
+		// Fisher-Yates shuffle driven by a small LCG so the order is scrambled but reproducible
+		let mut seed: u64 = 1347;
+		for index in (1..files.len()).rev() {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			files.swap(index, (seed >> 33) as usize % (index + 1));
+		}
+
+		let expected = files.iter().map(|file| (file.path.clone(), file.content.clone())).collect::<Vec<_>>();
+		let result = parse_batch_with_config(files, &ParserConfig::default());
+
+		assert_eq!(result.len(), expected.len());
+		for (file, (path, content)) in result.iter().zip(expected) {
+			assert_eq!(file.path, path);
+			let description = &file.liquid_types.as_ref().unwrap().success[0].description;
+			assert_eq!(format!("{{% doc %}}{description}{{% enddoc %}}"), content);
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn parse_batch_map_test() {
+		let results = parse_batch_map(vec![
+			FileInput::new("snippets/a.liquid", "{% doc %}A{% enddoc %}"),
+			FileInput::new("snippets/b.liquid", "<p>No docs</p>"),
+			FileInput::new("snippets/c.liquid", "{% doc %}@param {unknown} foo{% enddoc %}"),
+			FileInput::new("snippets/a.liquid", "{% doc %}A2{% enddoc %}"),
+		]);
+
+		assert_eq!(results.len(), 3);
+		assert_eq!(results["snippets/a.liquid"].success[0].description, "A2");
+		assert_eq!(results["snippets/b.liquid"].success_count(), 0);
+		assert_eq!(results["snippets/b.liquid"].error_count(), 0);
+		assert!(!results.contains_key("snippets/d.liquid"));
+		assert_eq!(results["snippets/c.liquid"].error_count(), 1);
+	}
+
+	#[test]
+	fn to_shopify_docs_url_test() {
+		assert_eq!(
+			to_shopify_docs_url("product"),
+			Some(String::from("https://shopify.dev/docs/api/liquid/objects/product"))
+		);
+		assert_eq!(to_shopify_docs_url("section_settings"), None);
+		assert_eq!(to_shopify_docs_url("string"), None);
+		assert_eq!(to_shopify_docs_url("unknown"), None);
+	}
+
+	#[test]
+	fn into_path_and_result_test() {
+		let mut files = parse_batch_with_config(
+			vec![
+				FileInput::new("snippets/a.liquid", "{% doc %}A{% enddoc %}"),
+				FileInput::new("snippets/b.liquid", "<p>No docs</p>"),
+			],
+			&ParserConfig::default(),
+		)
+		.into_iter()
+		.map(LiquidFile::into_path_and_result);
+
+		let (path, result) = files.next().unwrap();
+		assert_eq!(path, "snippets/a.liquid");
+		assert_eq!(result.unwrap().success[0].description, "A");
+		assert_eq!(files.next(), Some((String::from("snippets/b.liquid"), None)));
+	}
+
+	#[test]
+	#[cfg(not(target_arch = "wasm32"))]
+	fn parse_batch_update_test() {
+		let previous = parse_batch_with_config(
+			vec![
+				FileInput::new("snippets/a.liquid", "{% doc %}A{% enddoc %}"),
+				FileInput::new("snippets/b.liquid", "{% doc %}B{% enddoc %}"),
+				FileInput::new("snippets/c.liquid", "{% doc %}C{% enddoc %}"),
+			],
+			&ParserConfig::default(),
+		);
+
+		let updated = parse_batch_update(
+			previous,
+			vec![
+				FileInput::new("snippets/d.liquid", "{% doc %}D{% enddoc %}"),
+				FileInput::new("snippets/c.liquid", "<p>No docs</p>"),
+			],
+			vec![
+				String::from("snippets/a.liquid"),
+				String::from("snippets/missing.liquid"),
+			],
+		);
+
+		assert_eq!(
+			updated.iter().map(|file| file.path.as_str()).collect::<Vec<_>>(),
+			vec!["snippets/b.liquid", "snippets/c.liquid", "snippets/d.liquid"]
+		);
+		assert_eq!(updated[0].liquid_types.as_ref().unwrap().success[0].description, "B");
+		assert!(updated[1].liquid_types.is_none());
+		assert_eq!(updated[2].liquid_types.as_ref().unwrap().success[0].description, "D");
+	}
+
+	#[test]
+	fn to_summary_json_test() {
+		let files = parse_batch_with_config(
+			vec![
+				FileInput::new("snippets/a.liquid", "{% doc %}A{% enddoc %}"),
+				FileInput::new("snippets/b.liquid", "<p>No docs</p>"),
+				FileInput::new("snippets/c.liquid", "{% doc %}\n@param {unknown} foo{% enddoc %}"),
+			],
+			&ParserConfig::default(),
+		);
+
+		assert_eq!(
+			to_summary_json(&files),
+			serde_json::json!({
+				"total": 3,
+				"documented": 1,
+				"undocumented": 2,
+				"errors": [{
+					"path": "snippets/c.liquid",
+					"errors": [{
+						"line": 2,
+						"column": 8,
+						"message": "Unknown parameter type on 2:8: \"unknown\"",
+						"code": "UnknownParameterType",
+					}],
+				}],
+			})
+		);
+		assert_eq!(
+			to_summary_json(&[]),
+			serde_json::json!({ "total": 0, "documented": 0, "undocumented": 0, "errors": [] })
+		);
+
+		// a file whose only doc block failed to parse has a doc tag but isn't documented
+		let stats = compute_batch_stats(&files);
+		assert_eq!(to_summary_json(&files)["documented"], stats.documented_files);
+		assert_eq!(to_summary_json(&files)["undocumented"], stats.undocumented_files);
+	}
+
+	#[test]
+	fn compute_batch_stats_test() {
+		let files = parse_batch_with_config(
+			vec![
+				FileInput::new("snippets/a.liquid", "{% doc %}@param a\n@param b\n@example\nA{% enddoc %}"),
+				FileInput::new("snippets/b.liquid", "<p>No docs</p>"),
+				FileInput::new(
+					"snippets/c.liquid",
+					"{% doc %}@param {unknown} foo{% enddoc %}{% doc %}@param {x} y{% enddoc %}",
+				),
+				FileInput::new("snippets/d.liquid", "{% doc %}@param d{% enddoc %}{% doc %}@param {x} y{% enddoc %}"),
+			],
+			&ParserConfig::default(),
+		);
+
+		assert_eq!(
+			compute_batch_stats(&files),
+			BatchStats {
+				total_files: 4,
+				documented_files: 2,
+				undocumented_files: 2,
+				error_files: 2,
+				total_params: 3,
+				total_examples: 1,
+				total_errors: 3,
+			}
+		);
+		assert_eq!(compute_batch_stats(&[]), BatchStats::default());
+	}
+
+	#[test]
+	fn line_and_column_test() {
+		let content = "{% doc %}\n  @param {unknown} foo\n{% enddoc %}";
+		assert_eq!(line_and_column(content, 0), (1, 1));
+		assert_eq!(line_and_column(content, 9), (1, 10));
+		assert_eq!(line_and_column(content, 10), (2, 1));
+		assert_eq!(line_and_column(content, 19), (2, 10));
+
+		let result = LiquidDocs::parse(content);
+		assert_eq!(
+			line_and_column(content, content.find("{unknown}").unwrap()),
+			(result.errors[0].line, result.errors[0].column)
+		);
+	}
+
+	#[test]
+	fn parse_result_metrics_test() {
+		let result = LiquidDocs::parse("{% doc %}@param foo{% enddoc %}{% doc %}@param {unknown} foo{% enddoc %}");
+		assert_eq!(result.success_count(), 1);
+		assert_eq!(result.error_count(), 1);
+		assert_eq!(result.coverage_ratio(), 0.5);
+
+		let result = LiquidDocs::parse("{% doc %}@param foo{% enddoc %}");
+		assert_eq!(result.coverage_ratio(), 1.0);
+
+		let result = LiquidDocs::parse("");
+		assert_eq!(result.success_count(), 0);
+		assert_eq!(result.error_count(), 0);
+		assert_eq!(result.coverage_ratio(), 1.0);
+	}
+
+	#[test]
+	fn find_lone_surrogate_test() {
+		let utf16 = |content: &str| content.encode_utf16().collect::<Vec<u16>>();
+		assert_eq!(find_lone_surrogate(utf16("{% doc %}Renders a card 🃏{% enddoc %}")), None);
+		assert_eq!(find_lone_surrogate(utf16("")), None);
+
+		let mut content = utf16("{% doc %}\n@param {string} ");
+		content.push(0xD800);
+		content.extend(utf16("title{% enddoc %}"));
+		assert_eq!(find_lone_surrogate(content), Some((2, 17)));
+
+		// a trailing surrogate without its leading half and columns counted in bytes like everywhere else
+		let mut content = utf16("<p>é</p>\n{% doc %}🃏");
+		content.push(0xDC00);
+		assert_eq!(find_lone_surrogate(content), Some((2, 14)));
+	}
+
+	#[test]
+	fn parse_replacement_character_test() {
+		// U+FFFD is valid text, lone surrogates are only caught at the WASM boundary
+		let result = LiquidDocs::parse("{% doc %}\n@param {string} title - The \u{FFFD} title{% enddoc %}");
+		assert!(result.errors.is_empty());
+		assert_eq!(result.success[0].param[0].description.as_deref(), Some("The \u{FFFD} title"));
+
+		// warnings are moved into the file the same way as errors
+		let result = LiquidDocs::parse("<p>Card</p>\n{% doc %}\n@param product{% enddoc %}");
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].line, 3);
+		assert!(result.warnings[0].message.contains("on line 3"));
+	}
+
+	#[test]
+	fn parse_result_find_test() {
+		let result = LiquidDocs::parse(
+			"{% doc %}Renders a Product Card\n@param title{% enddoc %}{% doc %}Renders an image\n@param title\n@param src{% enddoc %}",
+		);
+
+		assert_eq!(result.find_block_by_description("product card").map(|block| block.param.len()), Some(1));
+		assert_eq!(
+			result.find_block_by_description("RENDERS").map(|block| block.description.as_str()),
+			Some("Renders a Product Card")
+		);
+		assert!(result.find_block_by_description("video").is_none());
+
+		assert_eq!(result.find_blocks_with_param("title").len(), 2);
+		assert_eq!(result.find_blocks_with_param("src")[0].description, "Renders an image");
+		assert!(result.find_blocks_with_param("alt").is_empty());
+	}
+
+	#[test]
+	fn check_backward_compat_test() {
+		let old = DocBlockBuilder::new()
+			.param(Param::default().with_name("title").with_type(ParamType::String))
+			.param(Param::default().with_name("image").with_type(ParamType::Shopify(String::from("image"))))
+			.param(Param::default().with_name("align").with_type(ParamType::Enum(vec![String::from("left")])).as_optional())
+			.param(Param::default().with_name("count").with_type(ParamType::Number).as_optional())
+			.param(Param::default().with_name("class").as_optional())
+			.param(Param::default().with_name("removed"))
+			.build();
+
+		let compatible = DocBlockBuilder::new()
+			.param(Param::default().with_name("title"))
+			.param(Param::default().with_name("image").with_type(ParamType::Object))
+			.param(
+				Param::default()
+					.with_name("align")
+					.with_type(ParamType::Enum(vec![String::from("left"), String::from("right")]))
+					.as_optional(),
+			)
+			.param(Param::default().with_name("count").with_type(ParamType::Number).as_optional())
+			.param(Param::default().with_name("removed").as_optional())
+			.build();
+		assert_eq!(
+			check_backward_compat(&old, &compatible),
+			CompatResult {
+				is_compatible: true,
+				breaking_changes: Vec::new(),
+			}
+		);
+
+		let breaking = DocBlockBuilder::new()
+			.param(Param::default().with_name("title").with_type(ParamType::Number))
+			.param(Param::default().with_name("image").with_type(ParamType::Shopify(String::from("image"))))
+			.param(Param::default().with_name("align").with_type(ParamType::Enum(vec![String::from("right")])).as_optional())
+			.param(Param::default().with_name("count").with_type(ParamType::Number))
+			.param(Param::default().with_name("class").with_type(ParamType::String).as_optional())
+			.param(Param::default().with_name("subtitle"))
+			.param(Param::default().with_name("badge").as_optional())
+			.build();
+		let result = check_backward_compat(&old, &breaking);
+		assert!(!result.is_compatible);
+		assert_eq!(
+			result.breaking_changes,
+			vec![
+				BreakingChange::ParamTypeChanged {
+					name: String::from("title"),
+					old_type: Some(ParamType::String),
+					new_type: Some(ParamType::Number),
+				},
+				BreakingChange::ParamTypeChanged {
+					name: String::from("align"),
+					old_type: Some(ParamType::Enum(vec![String::from("left")])),
+					new_type: Some(ParamType::Enum(vec![String::from("right")])),
+				},
+				BreakingChange::OptionalParamMadeRequired {
+					name: String::from("count")
+				},
+				BreakingChange::ParamTypeChanged {
+					name: String::from("class"),
+					old_type: None,
+					new_type: Some(ParamType::String),
+				},
+				BreakingChange::RequiredParamRemoved {
+					name: String::from("removed")
+				},
+				BreakingChange::RequiredParamAdded {
+					name: String::from("subtitle")
+				},
+			]
+		);
+		assert_eq!(result.breaking_changes[4].to_string(), "Required param \"removed\" was removed");
+		assert_eq!(result.breaking_changes[5].to_string(), "Required param \"subtitle\" was added");
+
+		// an `any` param accepts whatever the old type was
+		let widened = DocBlockBuilder::new()
+			.param(Param::default().with_name("title").with_type(ParamType::Any))
+			.param(Param::default().with_name("image").with_type(ParamType::Any))
+			.param(Param::default().with_name("align").with_type(ParamType::Any).as_optional())
+			.param(Param::default().with_name("count").with_type(ParamType::Any).as_optional())
+			.param(Param::default().with_name("class").with_type(ParamType::Any).as_optional())
+			.param(Param::default().with_name("removed").with_type(ParamType::Any))
+			.build();
+		assert!(check_backward_compat(&old, &widened).is_compatible);
+	}
+
+	#[test]
+	fn parse_with_config_max_params_test() {
+		let content = "{% doc %}@param a\n@param b{% enddoc %}{% doc %}@param a\n@param b\n@param c{% enddoc %}";
+		let config = ParserConfig {
+			max_params: Some(2),
+			..Default::default()
+		};
+
+		let result = parse_with_config(content, &config);
+		assert_eq!(result.success_count(), 1);
+		assert_eq!(result.total_param_count(), 2);
+		assert_eq!(
+			result.errors[0].message,
+			ParsingError::TooManyParams {
+				line: 4,
+				column: 1,
+				count: 3,
+				limit: 2
+			}
+			.to_string()
+		);
+		assert_eq!((result.errors[0].line, result.errors[0].column), (4, 1));
+
+		assert_eq!(LiquidDocs::parse(content).total_param_count(), 5);
+	}
+
+	#[test]
+	fn parse_result_into_vec_test() {
+		let doc_blocks = LiquidDocs::parse("{% doc %}A{% enddoc %}{% doc %}@param {unknown} b{% enddoc %}").into_vec();
+		assert_eq!(doc_blocks.len(), 1);
+		assert_eq!(doc_blocks[0].description, "A");
+	}
+
+	#[test]
+	fn parse_result_into_iter_test() {
+		let result = LiquidDocs::parse("{% doc %}@param {unknown} foo{% enddoc %}{% doc %}@param foo{% enddoc %}");
+
+		let borrowed = (&result).into_iter().collect::<Vec<_>>();
+		assert_eq!(borrowed.len(), 2);
+		assert_eq!(borrowed[0].map(|block| block.param[0].name.as_str()), Ok("foo"));
+		assert_eq!(borrowed[1].map(|_| ()), Err("Unknown parameter type on 1:17: \"unknown\""));
+
+		let mut count = 0;
+		for item in &result {
+			assert_eq!(item.is_ok(), count == 0);
+			count += 1;
+		}
+		assert_eq!(count, 2);
+
+		let owned = result.into_iter().collect::<Vec<_>>();
+		assert_eq!(owned[0].as_ref().map(|block| block.param.len()), Ok(1));
+		assert_eq!(owned[1], Err(String::from("Unknown parameter type on 1:17: \"unknown\"")));
+	}
+
+	#[test]
+	fn param_type_serialization_test() {
+		let cases = [
+			(ParamType::String, r#""string""#),
+			(ParamType::Number, r#""number""#),
+			(ParamType::Boolean, r#""boolean""#),
+			(ParamType::Object, r#""object""#),
+			(ParamType::Any, r#""any""#),
+			(ParamType::ArrayOf(Box::new(ParamType::Any)), r#""any[]""#),
+			(ParamType::Shopify(String::from("currency")), r#""currency""#),
+			(ParamType::ArrayOf(Box::new(ParamType::String)), r#""string[]""#),
+			(
+				ParamType::ArrayOf(Box::new(ParamType::ArrayOf(Box::new(ParamType::Shopify(String::from("product")))))),
+				r#""product[][]""#,
+			),
+			(
+				ParamType::Enum(vec![String::from("left"), String::from("right")]),
+				r#"{"type":"enum","values":["left","right"]}"#,
+			),
+			(
+				ParamType::ArrayOf(Box::new(ParamType::Enum(vec![String::from("left")]))),
+				r#"{"type":"enum[]","values":["left"]}"#,
+			),
+			(ParamType::NamedObject(String::from("article-card")), r#"{"type":"object","name":"article-card"}"#),
+			(
+				ParamType::ArrayOf(Box::new(ParamType::NamedObject(String::from("article-card")))),
+				r#"{"type":"object[]","name":"article-card"}"#,
+			),
+			(
+				ParamType::ArrayOf(Box::new(ParamType::Constrained {
+					base: ConstrainedBase::Number,
+					constraints: BTreeMap::from([
+						(String::from("min"), String::from("0")),
+						(String::from("max"), String::from("10")),
+					]),
+				})),
+				r#"{"type":"number[]","constraints":{"max":"10","min":"0"}}"#,
+			),
+		];
+
+		for (param_type, json) in cases {
+			assert_eq!(serde_json::to_string(&param_type).unwrap(), json);
+			assert_eq!(serde_json::from_str::<ParamType>(json).unwrap(), param_type);
+		}
+
+		assert!(serde_json::from_str::<ParamType>(r#"{"type":"union","values":["left"]}"#).is_err());
+		assert!(serde_json::from_str::<ParamType>(r#"{"type":"string","name":"article-card"}"#).is_err());
+		assert!(serde_json::from_str::<ParamType>(r#"{"type":"boolean","constraints":{}}"#).is_err());
+		assert!(serde_json::from_str::<ParamType>(r#""[]""#).is_err());
+		assert!(serde_json::from_str::<ParamType>(r#"{"ArrayOf":"Number"}"#).is_err());
+	}
+
+	#[test]
+	fn doc_block_field_order_test() {
+		let doc_block = DocBlock::with_description("Card")
+			.with_param(Param::default().with_name("title").with_type(ParamType::String).with_description("The title"))
+			.with_example("{% render 'card' %}");
+
+		assert_eq!(
+			serde_json::to_string(&doc_block).unwrap(),
+			concat!(
+				r#"{"description":"Card","deprecated":null,"#,
+				r#""param":[{"name":"title","description":"The title","type":"string","optional":false,"span":null}],"#,
+				r#""param_groups":[],"links":[],"#,
+				r#""example":[{"body":"{% render 'card' %}","span":{"start":0,"end":0}}]}"#,
+			)
+		);
+	}
+
+	#[test]
+	fn parse_result_round_trip_test() {
+		let config = ParserConfig {
+			named_object_schemas: vec![String::from("article-card")],
+			..ParserConfig::default()
+		};
+		let result = LiquidDocs::parse_with_config(
+			concat!(
+				"{% doc %}\n",
+				"  Renders a card\n",
+				"  @param {string} a\n",
+				"  @param {number} b\n",
+				"  @param {boolean} c\n",
+				"  @param {object} d\n",
+				"  @param {product[][]} e\n",
+				"  @param {enum: \"left\"|\"right\"} f\n",
+				"  @param {any} g\n",
+				"  @param {object: \"article-card\"} h\n",
+				"  @param {string: min=1, max=100} [i] - The label\n",
+				"  @example\n",
+				"  {% render 'card' %}\n",
+				"{% enddoc %}\n",
+				"{% doc %}@param {unknown} foo{% enddoc %}\n",
+				"{% doc %}\nCard\n@example\n{% enddoc %}",
+			),
+			&config,
+		);
+		assert_eq!(result.success.len(), 2);
+		assert_eq!(result.success[0].param_count(), 9);
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.warnings.len(), 1);
+
+		let json = serde_json::to_string(&result).unwrap();
+		assert_eq!(serde_json::from_str::<ParseResult>(&json).unwrap(), result);
+	}
+
+	#[test]
+	fn liquid_file_round_trip_test() {
+		let files = parse_batch_with_config(
+			vec![
+				FileInput::new("a.liquid", "{% doc %}Card\n@param {product[]} items\n@example\n{% render 'a' %}{% enddoc %}"),
+				FileInput::new("b.liquid", "{% doc %}@param {unknown} foo{% enddoc %}"),
+				FileInput::new("c.liquid", "<p>No docs</p>"),
+			],
+			&ParserConfig::default(),
+		);
+
+		let json = serde_json::to_string(&files).unwrap();
+		assert_eq!(serde_json::from_str::<Vec<LiquidFile>>(&json).unwrap(), files);
+		assert_ne!(files[0], files[1]);
+	}
+
+	#[test]
+	fn doc_coverage_test() {
+		let files = parse_batch_with_config(
+			vec![
+				FileInput::new("a.liquid", "{% doc %}@param foo{% enddoc %}"),
+				FileInput::new("b.liquid", "<p>No docs</p>"),
+				FileInput::new("c.liquid", "{% doc %}@param {unknown} foo{% enddoc %}"),
+				FileInput::new("d.liquid", "{% doc %}Card{% enddoc %}"),
+			],
+			&ParserConfig::default(),
+		);
+
+		assert_eq!(files.iter().map(LiquidFile::is_documented).collect::<Vec<_>>(), vec![true, false, false, true]);
+		assert!(files[2].liquid_types.is_some());
+		assert_eq!(files.iter().map(LiquidFile::has_parse_errors).collect::<Vec<_>>(), vec![false, false, true, false]);
+		assert_eq!(files.iter().map(LiquidFile::doc_coverage_ratio).collect::<Vec<_>>(), vec![1.0, 0.0, 0.0, 1.0]);
+		assert_eq!(batch_coverage(&files), 0.5);
+		assert_eq!(batch_coverage(&[]), 1.0);
+	}
+
+	#[test]
+	fn parser_config_default_test() {
+		let config = ParserConfig::default();
+		assert_eq!(config.max_doc_block_bytes, None);
+		assert!(!config.measure_timing);
+		assert_eq!(config.max_array_depth, 5);
+		assert_eq!(config.max_params, None);
+		assert!(!config.validate_example_syntax);
+		assert_eq!(config.tab_width, 4);
+		assert_eq!(config.column_mode, ColumnMode::Bytes);
+		assert!(config.warn_on_shadowing);
+		assert!(config.named_object_schemas.is_empty());
+
+		let config = ParserConfig {
+			max_doc_block_bytes: Some(65536),
+			..Default::default()
+		};
+		assert_eq!(config.max_doc_block_bytes, Some(65536));
+		assert_eq!(config.max_array_depth, 5);
+	}
+
+	#[test]
+	fn parse_with_config_max_doc_block_bytes_test() {
+		let content = "{% doc %}@param foo - bar{% enddoc %}\n{% doc %}@param foo{% enddoc %}";
+
+		let result = parse_with_config(content, &ParserConfig::default());
+		assert_eq!(result.success.len(), 2);
+		assert!(result.errors.is_empty());
+
+		let result = parse_with_config(
+			content,
+			&ParserConfig {
+				max_doc_block_bytes: Some(10),
+				..Default::default()
+			},
+		);
+		assert_eq!(result.success.len(), 1);
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.errors[0].message, "Doc block is 16 bytes which exceeds the limit of 10 bytes");
+		assert_eq!((result.errors[0].line, result.errors[0].column), (1, 10));
+
+		let result = parse_with_config(
+			"<p>Card</p>\n  {% doc %}@param foo - bar{% enddoc %}",
+			&ParserConfig {
+				max_doc_block_bytes: Some(10),
+				..Default::default()
+			},
+		);
+		assert_eq!((result.errors[0].line, result.errors[0].column), (2, 12));
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn parse_batch_with_config_measure_timing_test() {
+		let files = || {
+			vec![
+				FileInput::new("a.liquid", "{% doc %}@param foo{% enddoc %}"),
+				FileInput::new("b.liquid", ""),
+			]
+		};
+
+		assert!(
+			parse_batch_with_config(files(), &ParserConfig::default()).iter().all(|file| file.parse_duration_ns.is_none())
+		);
+
+		let config = ParserConfig {
+			measure_timing: true,
+			..Default::default()
+		};
+		assert!(parse_batch_with_config(files(), &config).iter().all(|file| file.parse_duration_ns.is_some()));
+	}
+
+	/// A directory below the system temp dir that is unique to this process so parallel runs don't race, it's removed
+	/// again when dropped even if the test fails
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	struct TempDir(std::path::PathBuf);
+
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	impl TempDir {
+		fn new(name: &str) -> Self {
+			let path = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+			std::fs::create_dir_all(&path).unwrap();
+			TempDir(path)
+		}
+	}
+
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	impl Drop for TempDir {
+		fn drop(&mut self) {
+			let _ = std::fs::remove_dir_all(&self.0);
+		}
+	}
+
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	#[test]
+	fn file_input_from_path_test() {
+		let temp_dir = TempDir::new("liquid-docs-from-path-test");
+		let path = temp_dir.0.join("card.liquid");
+		std::fs::write(&path, "{% doc %}Card{% enddoc %}").unwrap();
+
+		let file = FileInput::from_path(&path).unwrap();
+		assert_eq!(file.path, path.to_string_lossy());
+		assert_eq!(file.content, "{% doc %}Card{% enddoc %}");
+
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(FileInput::from_path(&path).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+	}
+
+	#[cfg(all(feature = "glob", not(target_arch = "wasm32")))]
+	#[test]
+	fn discover_liquid_files_test() {
+		let temp_dir = TempDir::new("liquid-docs-discover-test");
+		let root = &temp_dir.0;
+		std::fs::create_dir_all(root.join("snippets/nested")).unwrap();
+		std::fs::write(root.join("snippets/card.liquid"), "{% doc %}Card{% enddoc %}").unwrap();
+		std::fs::write(root.join("snippets/nested/image.liquid"), "{% doc %}Image{% enddoc %}").unwrap();
+		std::fs::write(root.join("snippets/readme.md"), "# Readme").unwrap();
+
+		let mut files = discover_liquid_files(root, DEFAULT_LIQUID_GLOB).unwrap();
+		files.sort_by(|a, b| a.path.cmp(&b.path));
+		assert_eq!(files.len(), 2);
+		assert!(files[0].path.ends_with("card.liquid"));
+		assert_eq!(files[0].content, "{% doc %}Card{% enddoc %}");
+		assert!(files[1].path.ends_with("image.liquid"));
+
+		assert_eq!(discover_liquid_files(root, "*.liquid").unwrap().len(), 0);
+		assert!(discover_liquid_files(root, "[").is_err());
+	}
 }
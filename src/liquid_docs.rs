@@ -1,6 +1,74 @@
+use alloc::{
+	borrow::Cow,
+	boxed::Box,
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
 use serde::Serialize;
 
-use crate::{DocBlock, Param, ParamType, shopify_liquid_objects::SHOPIFY_ALLOWED_OBJECTS};
+use crate::{
+	ColumnMode, ConstrainedBase, DeprecatedInfo, DocBlock, DocLink, ExampleBlock, ParamGroup, ParamRef, ParamType,
+	ParseError, ParseResult, ParserConfig, Span, line_and_column, shopify_liquid_objects::SHOPIFY_ALLOWED_OBJECTS,
+};
+
+/// All tags that end the content of the tag before them
+const TAGS: [&str; 6] = [
+	"@param",
+	"@param-group",
+	"@example",
+	"@description",
+	"@deprecated",
+	"@link",
+];
+
+/// Longest part of a line we quote in errors
+const MAX_OFFENDING_LINE_BYTES: usize = 200;
+
+/// Pseudo-types for the settings schema object of a section or block e.g. `section.settings`, they are no Shopify objects
+/// so they are not part of the generated [SHOPIFY_ALLOWED_OBJECTS]
+pub(crate) const SETTINGS_OBJECTS: [&str; 2] = ["section_settings", "block_settings"];
+
+/// Param types the parser matches before it looks up Shopify objects so [SHOPIFY_ALLOWED_OBJECTS] must not contain them
+const PRIMITIVE_TYPES: [&str; 4] = ["string", "number", "boolean", "object"];
+
+/// Global objects every Liquid template can access, they aren't part of the generated [SHOPIFY_ALLOWED_OBJECTS] because
+/// that list only contains the objects that aren't global
+const LIQUID_GLOBALS: [&str; 32] = [
+	"additional_checkout_buttons",
+	"all_country_option_tags",
+	"all_products",
+	"articles",
+	"blogs",
+	"canonical_url",
+	"cart",
+	"collections",
+	"content_for_additional_checkout_buttons",
+	"content_for_header",
+	"content_for_index",
+	"content_for_layout",
+	"country_option_tags",
+	"current_page",
+	"customer",
+	"handle",
+	"images",
+	"linklists",
+	"localization",
+	"metaobjects",
+	"page_description",
+	"page_image",
+	"page_title",
+	"pages",
+	"powered_by_link",
+	"request",
+	"routes",
+	"scripts",
+	"settings",
+	"shop",
+	"template",
+	"theme",
+];
 
 /// The error types our [LiquidDocs] methods could throw
 #[derive(Debug, PartialEq, Serialize)]
@@ -25,11 +93,41 @@ pub enum ParsingError {
 		column: usize,
 		offending_type: String,
 	},
+	InvalidParameterName {
+		line: usize,
+		column: usize,
+		offending_line: String,
+	},
+	DocBlockTooLarge {
+		line: usize,
+		column: usize,
+		size: usize,
+		limit: usize,
+	},
+	ArrayNestingTooDeep {
+		line: usize,
+		column: usize,
+		depth: usize,
+		max: usize,
+	},
+	TooManyParams {
+		line: usize,
+		column: usize,
+		count: usize,
+		limit: usize,
+	},
+	ReadError {
+		message: String,
+	},
+	InvalidUtf8 {
+		line: usize,
+		column: usize,
+	},
 	NoDocContentFound,
 }
 
-impl std::fmt::Display for ParsingError {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for ParsingError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		match self {
 			ParsingError::MissingParameterName {
 				line,
@@ -58,18 +156,93 @@ impl std::fmt::Display for ParsingError {
 				column,
 				offending_type,
 			} => write!(f, "Unknown parameter type on {line}:{column}: \"{offending_type}\"",),
+			ParsingError::InvalidParameterName {
+				line,
+				column,
+				offending_line,
+			} => write!(f, "Invalid parameter name on {line}:{column} near this line:\n{offending_line}"),
+			ParsingError::DocBlockTooLarge { size, limit, .. } => {
+				write!(f, "Doc block is {size} bytes which exceeds the limit of {limit} bytes")
+			},
+			ParsingError::ArrayNestingTooDeep { depth, max, .. } => {
+				write!(f, "Parameter type is nested {depth} arrays deep which exceeds the maximum of {max}")
+			},
+			ParsingError::TooManyParams { count, limit, .. } => {
+				write!(f, "Doc block has {count} params which exceeds the limit of {limit} params")
+			},
+			ParsingError::ReadError { message } => write!(f, "Failed to read doc content: {message}"),
+			ParsingError::InvalidUtf8 { line, column } => write!(
+				f,
+				"Invalid UTF-8 on {line}:{column}: the input contained a character that could not be converted e.g. a lone UTF-16 surrogate"
+			),
 			ParsingError::NoDocContentFound => write!(f, "No doc content found"),
 		}
 	}
 }
 
+impl core::error::Error for ParsingError {}
+
 impl ParsingError {
+	/// Count the column of this error in characters of the given content instead of bytes when asked to
+	fn with_column_mode(mut self, content: &str, column_mode: ColumnMode) -> Self {
+		if column_mode == ColumnMode::Bytes {
+			return self;
+		}
+
+		match &mut self {
+			ParsingError::MissingParameterName { line, column, .. }
+			| ParsingError::MissingOptionalClosingBracket { line, column, .. }
+			| ParsingError::UnexpectedParameterEnd { line, column, .. }
+			| ParsingError::UnknownParameterType { line, column, .. }
+			| ParsingError::InvalidParameterName { line, column, .. }
+			| ParsingError::DocBlockTooLarge { line, column, .. }
+			| ParsingError::ArrayNestingTooDeep { line, column, .. }
+			| ParsingError::TooManyParams { line, column, .. }
+			| ParsingError::InvalidUtf8 { line, column } => {
+				let line_start = content.split_inclusive('\n').take(*line - 1).map(str::len).sum::<usize>();
+				if let Some(before) = content.get(line_start..line_start + *column - 1) {
+					*column = before.chars().count() + 1;
+				}
+			},
+			ParsingError::ReadError { .. } | ParsingError::NoDocContentFound => {},
+		}
+		self
+	}
+
+	/// Move the line and column of this error from a doc block that starts on `start_line`:`start_column` of its file
+	fn offset_position(mut self, start_line: usize, start_column: usize) -> Self {
+		match &mut self {
+			ParsingError::MissingParameterName { line, column, .. }
+			| ParsingError::MissingOptionalClosingBracket { line, column, .. }
+			| ParsingError::UnexpectedParameterEnd { line, column, .. }
+			| ParsingError::UnknownParameterType { line, column, .. }
+			| ParsingError::InvalidParameterName { line, column, .. }
+			| ParsingError::ArrayNestingTooDeep { line, column, .. }
+			| ParsingError::TooManyParams { line, column, .. }
+			| ParsingError::InvalidUtf8 { line, column } => {
+				// only the first line of the doc block shares its line with the content before it
+				if *line == 1 {
+					*column += start_column - 1;
+				}
+				*line += start_line - 1;
+			},
+			ParsingError::DocBlockTooLarge { .. } | ParsingError::ReadError { .. } | ParsingError::NoDocContentFound => {},
+		}
+		self
+	}
+
 	pub fn get_line(&self) -> usize {
 		match self {
 			ParsingError::MissingParameterName { line, .. } => *line,
 			ParsingError::MissingOptionalClosingBracket { line, .. } => *line,
 			ParsingError::UnexpectedParameterEnd { line, .. } => *line,
 			ParsingError::UnknownParameterType { line, .. } => *line,
+			ParsingError::InvalidParameterName { line, .. } => *line,
+			ParsingError::DocBlockTooLarge { line, .. } => *line,
+			ParsingError::ArrayNestingTooDeep { line, .. } => *line,
+			ParsingError::TooManyParams { line, .. } => *line,
+			ParsingError::ReadError { .. } => 0,
+			ParsingError::InvalidUtf8 { line, .. } => *line,
 			ParsingError::NoDocContentFound => 0,
 		}
 	}
@@ -80,20 +253,270 @@ impl ParsingError {
 			ParsingError::MissingOptionalClosingBracket { column, .. } => *column,
 			ParsingError::UnexpectedParameterEnd { column, .. } => *column,
 			ParsingError::UnknownParameterType { column, .. } => *column,
+			ParsingError::InvalidParameterName { column, .. } => *column,
+			ParsingError::DocBlockTooLarge { column, .. } => *column,
+			ParsingError::ArrayNestingTooDeep { column, .. } => *column,
+			ParsingError::TooManyParams { column, .. } => *column,
+			ParsingError::ReadError { .. } => 0,
+			ParsingError::InvalidUtf8 { column, .. } => *column,
 			ParsingError::NoDocContentFound => 0,
 		}
 	}
+
+	/// A stable identifier of the error kind e.g. `"InvalidUtf8"` for tools that match on errors without their message
+	pub fn get_code(&self) -> &'static str {
+		match self {
+			ParsingError::MissingParameterName { .. } => "MissingParameterName",
+			ParsingError::MissingOptionalClosingBracket { .. } => "MissingOptionalClosingBracket",
+			ParsingError::UnexpectedParameterEnd { .. } => "UnexpectedParameterEnd",
+			ParsingError::UnknownParameterType { .. } => "UnknownParameterType",
+			ParsingError::InvalidParameterName { .. } => "InvalidParameterName",
+			ParsingError::DocBlockTooLarge { .. } => "DocBlockTooLarge",
+			ParsingError::ArrayNestingTooDeep { .. } => "ArrayNestingTooDeep",
+			ParsingError::TooManyParams { .. } => "TooManyParams",
+			ParsingError::ReadError { .. } => "ReadError",
+			ParsingError::InvalidUtf8 { .. } => "InvalidUtf8",
+			ParsingError::NoDocContentFound => "NoDocContentFound",
+		}
+	}
+
+	/// Wrap the error with `context_lines` lines before and after the line it occurred on and a `^` pointing at its column
+	///
+	/// Pass the [ColumnMode] the error was parsed with so the column is read the same way it was counted.
+	pub fn with_context(self, content: &str, context_lines: usize, column_mode: ColumnMode) -> ParsingErrorWithContext {
+		let (line, column) = (self.get_line(), self.get_column());
+		let lines = content.lines().collect::<Vec<_>>();
+
+		if line == 0 || line > lines.len() {
+			return ParsingErrorWithContext {
+				error: self,
+				snippet: String::new(),
+			};
+		}
+
+		let first_line = line.saturating_sub(context_lines).max(1);
+		let last_line = (line + context_lines).min(lines.len());
+		let gutter_width = last_line.to_string().len();
+
+		let mut snippet = Vec::new();
+		for (idx, content_line) in lines.iter().enumerate().take(last_line).skip(first_line - 1) {
+			snippet.push(format!("{:>gutter_width$} | {content_line}", idx + 1));
+
+			if idx + 1 == line {
+				// column is 1 indexed and we mirror tabs to keep the pointer aligned with the content above
+				let prefix = match column_mode {
+					ColumnMode::Bytes => content_line.get(..column.saturating_sub(1)).unwrap_or(content_line),
+					ColumnMode::Characters => content_line
+						.char_indices()
+						.nth(column.saturating_sub(1))
+						.map_or(*content_line, |(pos, _)| &content_line[..pos]),
+				};
+				let pointer_indent = prefix.chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect::<String>();
+				snippet.push(format!("{:>gutter_width$} | {pointer_indent}^", ""));
+			}
+		}
+
+		ParsingErrorWithContext {
+			error: self,
+			snippet: snippet.join("\n"),
+		}
+	}
+}
+
+/// A [ParsingError] together with the lines of content surrounding it
+#[derive(Debug, PartialEq)]
+pub struct ParsingErrorWithContext {
+	pub error: ParsingError,
+	pub snippet: String,
+}
+
+impl core::fmt::Display for ParsingErrorWithContext {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		if self.snippet.is_empty() {
+			write!(f, "{}", self.error)
+		} else {
+			write!(f, "{}\n{}", self.error, self.snippet)
+		}
+	}
+}
+
+impl core::error::Error for ParsingErrorWithContext {
+	fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+/// The warning types our [LiquidDocs] methods could emit without failing the parse
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ParseWarning {
+	MixedPositionalParams {
+		line: usize,
+	},
+	EmptyExample {
+		line: usize,
+	},
+	MalformedExampleSyntax {
+		example_index: usize,
+		line: usize,
+		detail: String,
+	},
+	ParamShadowsBuiltin {
+		name: String,
+		line: usize,
+	},
+}
+
+impl core::fmt::Display for ParseWarning {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self {
+			ParseWarning::MixedPositionalParams { line } => {
+				write!(f, "Positional and named params are mixed in the same doc block on line {line}")
+			},
+			ParseWarning::EmptyExample { line } => write!(f, "Empty @example on line {line}"),
+			ParseWarning::MalformedExampleSyntax {
+				example_index,
+				line,
+				detail,
+			} => {
+				write!(f, "Malformed Liquid in @example number {} on line {line}: {detail}", example_index + 1)
+			},
+			ParseWarning::ParamShadowsBuiltin { name, line } => {
+				write!(f, "Param \"{name}\" on line {line} shadows the built-in Liquid object of the same name")
+			},
+		}
+	}
+}
+
+impl ParseWarning {
+	pub fn get_line(&self) -> usize {
+		match self {
+			ParseWarning::MixedPositionalParams { line } => *line,
+			ParseWarning::EmptyExample { line } => *line,
+			ParseWarning::MalformedExampleSyntax { line, .. } => *line,
+			ParseWarning::ParamShadowsBuiltin { line, .. } => *line,
+		}
+	}
+
+	pub fn get_column(&self) -> usize {
+		match self {
+			ParseWarning::MixedPositionalParams { .. } => 0,
+			ParseWarning::EmptyExample { .. } => 0,
+			ParseWarning::MalformedExampleSyntax { .. } => 0,
+			ParseWarning::ParamShadowsBuiltin { .. } => 0,
+		}
+	}
+
+	/// Move the line of this warning from a doc block that starts on `start_line` of its file
+	fn offset_line(mut self, start_line: usize) -> Self {
+		match &mut self {
+			ParseWarning::MixedPositionalParams { line }
+			| ParseWarning::EmptyExample { line }
+			| ParseWarning::ParamShadowsBuiltin { line, .. } => *line += start_line - 1,
+			// example syntax is checked with the spans of the examples which already point into the file
+			ParseWarning::MalformedExampleSyntax { .. } => {},
+		}
+		self
+	}
+
+	/// A stable identifier of the warning kind e.g. `"EmptyExample"`
+	pub fn get_code(&self) -> &'static str {
+		match self {
+			ParseWarning::MixedPositionalParams { .. } => "MixedPositionalParams",
+			ParseWarning::EmptyExample { .. } => "EmptyExample",
+			ParseWarning::MalformedExampleSyntax { .. } => "MalformedExampleSyntax",
+			ParseWarning::ParamShadowsBuiltin { .. } => "ParamShadowsBuiltin",
+		}
+	}
 }
 
 /// The main struct that parses the content of liquid files
 pub struct LiquidDocs<'a> {
 	content: &'a str,
-	chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+	chars: core::iter::Peekable<core::str::CharIndices<'a>>,
 }
 
 impl<'a> LiquidDocs<'a> {
+	/// Extract and parse all doc blocks of the given Liquid code in one step
+	pub fn parse(content: &'a str) -> ParseResult {
+		Self::parse_with_config(content, &ParserConfig::default())
+	}
+
+	/// Extract and parse all doc blocks of the given Liquid code with a custom [ParserConfig]
+	pub fn parse_with_config(content: &'a str, config: &ParserConfig) -> ParseResult {
+		let mut result = ParseResult {
+			success: Vec::new(),
+			errors: Vec::new(),
+			warnings: Vec::new(),
+		};
+
+		if let Some(blocks) = Self::extract_doc_blocks_with_spans(content) {
+			for (span, block) in blocks {
+				// errors and warnings point into the whole file so they can be reported next to its path
+				let (start_line, start_column) = line_and_column(content, span.start);
+
+				if let Some(limit) = config.max_doc_block_bytes
+					&& block.len() > limit
+				{
+					let error = ParsingError::DocBlockTooLarge {
+						line: start_line,
+						column: start_column,
+						size: block.len(),
+						limit,
+					};
+					result.errors.push(ParseError::from(error.with_column_mode(content, config.column_mode)));
+					continue;
+				}
+
+				let to_file_position = |error: ParsingError| {
+					error.offset_position(start_line, start_column).with_column_mode(content, config.column_mode)
+				};
+
+				let mut warnings = Vec::new();
+				match Self::parse_doc_content_recovering(block, span.start, config, None, &mut warnings) {
+					Ok(block_type) => {
+						result
+							.warnings
+							.extend(warnings.into_iter().map(|warning| ParseError::from(warning.offset_line(start_line))));
+						if config.validate_example_syntax {
+							for (example_index, example) in block_type.example.iter().enumerate() {
+								if let Some((body_line, detail)) = Self::check_example_syntax(&example.body) {
+									// the span starts at the `@example` tag and its body at the first line with content after it
+									let (tag_line, _) = line_and_column(content, example.span.start);
+									let after_tag = &content[example.span.start + "@example".len()..example.span.end];
+									let leading_lines = after_tag[..after_tag.len() - after_tag.trim_start().len()].matches('\n').count();
+									result.warnings.push(ParseError::from(ParseWarning::MalformedExampleSyntax {
+										example_index,
+										line: tag_line + leading_lines + body_line - 1,
+										detail,
+									}));
+								}
+							}
+						}
+						result.success.push(block_type);
+					},
+					Err(error) => result.errors.push(ParseError::from(to_file_position(error))),
+				}
+			}
+		}
+
+		result
+	}
+
 	/// Extract a collection of all doc blocks from the given content without the wrapping doc tag
 	pub fn extract_doc_blocks(content: &'a str) -> Option<Vec<&'a str>> {
+		Self::extract_doc_blocks_with_spans(content).map(|blocks| blocks.into_iter().map(|(_, block)| block).collect())
+	}
+
+	/// Extract a collection of all doc blocks together with the span of their content within the given content
+	///
+	/// Like Liquid we read the tag name up to the first non-word character and ignore the rest of the opening tag so
+	/// `{% doc extra-content %}` still opens a doc block while `{% docs %}` doesn't. That also covers misplaced
+	/// whitespace control like `{% doc- %}`. A dash only counts as whitespace control directly after `{%` so
+	/// `{% - doc %}` is not a doc tag.
+	///
+	/// The content is returned verbatim even when the opening tag ends in `-%}`. Doc tags never render so whitespace
+	/// control has no effect on them and keeping the content as is lets the spans point at the exact source.
+	pub fn extract_doc_blocks_with_spans(content: &'a str) -> Option<Vec<(Span, &'a str)>> {
 		// This may find more than just the closing tags for our doc blocks which means we sometimes may not return early
 		// but that's still better then never returning early
 		let possible_doc_blocks = content.matches("enddoc").count();
@@ -102,6 +525,16 @@ impl<'a> LiquidDocs<'a> {
 			return None;
 		}
 
+		// `enddoc` may only appear inside comments or strings so we also bail early when no tag could open a doc block
+		let has_doc_tag = content.match_indices("{%").any(|(index, _)| {
+			let rest = content[index + 2..].trim_start_matches('-').trim_start();
+			// tag names are matched case-insensitively like in the scanner below
+			rest.get(..3).is_some_and(|name| name.eq_ignore_ascii_case("doc"))
+		});
+		if !has_doc_tag {
+			return None;
+		}
+
 		let mut parser = Self {
 			content,
 			chars: content.char_indices().peekable(),
@@ -110,7 +543,25 @@ impl<'a> LiquidDocs<'a> {
 		let mut blocks = Vec::with_capacity(possible_doc_blocks);
 		let mut found_blocks = 0;
 
-		while let Some((_, ch)) = parser.chars.next() {
+		while let Some((pos, ch)) = parser.chars.next() {
+			// JSON and JavaScript inside `<script>` may contain `{% doc %}` in strings, an unclosed one is scanned as usual
+			if ch == '<'
+				&& Self::starts_with_html_tag(&content[pos..], "script")
+				&& let Some(end) = Self::find_closing_html_tag(&content[pos..], "script")
+			{
+				while parser.chars.next_if(|&(next_pos, _)| next_pos < pos + end).is_some() {}
+				continue;
+			}
+
+			// `{# inline comment #}`, an unclosed one is scanned as usual
+			if ch == '{'
+				&& parser.chars.peek().map(|(_, c)| *c) == Some('#')
+				&& let Some(end) = content[pos..].find("#}")
+			{
+				while parser.chars.next_if(|&(next_pos, _)| next_pos < pos + end).is_some() {}
+				continue;
+			}
+
 			if ch == '{' && parser.chars.peek().map(|(_, c)| *c) == Some('%') {
 				parser.chars.next(); // consume '%'
 				parser.skip_dash();
@@ -121,6 +572,12 @@ impl<'a> LiquidDocs<'a> {
 					continue;
 				}
 
+				// `{% liquid %}` has no end tag, its code runs until the tag closes and can't open a doc block
+				if parser.peek_matches("liquid") {
+					parser.consume_till_tag_close();
+					continue;
+				}
+
 				if parser.peek_matches("raw") {
 					parser.skip_to_tag("endraw", true);
 					continue;
@@ -135,7 +592,13 @@ impl<'a> LiquidDocs<'a> {
 					parser.consume_chars(3);
 					let doc_content_start = parser.consume_till_tag_close()?;
 					let doc_content_end = parser.skip_to_tag("enddoc", false)?;
-					blocks.push(&content[doc_content_start..doc_content_end]);
+					blocks.push((
+						Span {
+							start: doc_content_start,
+							end: doc_content_end,
+						},
+						&content[doc_content_start..doc_content_end],
+					));
 					found_blocks += 1;
 				}
 			}
@@ -150,6 +613,70 @@ impl<'a> LiquidDocs<'a> {
 
 	/// Parse doc block content
 	pub fn parse_doc_content(content: &'a str) -> Result<DocBlock, ParsingError> {
+		Self::parse_doc_content_with_offset(content, 0)
+	}
+
+	/// Parse doc block content that starts `offset` bytes into its file so all spans point into that file
+	pub fn parse_doc_content_with_offset(content: &'a str, offset: usize) -> Result<DocBlock, ParsingError> {
+		Self::parse_doc_content_with_config(content, offset, &ParserConfig::default())
+	}
+
+	/// Parse the doc block content that starts `byte_offset` bytes into `file` e.g. at a span from
+	/// [LiquidDocs::extract_doc_blocks_with_spans] so spans and the line and column of errors point into the whole file
+	///
+	/// Like all parsing with the default [ParserConfig] error columns count bytes, see [ParserConfig::column_mode].
+	pub fn parse_doc_content_at_offset(file: &'a str, byte_offset: usize) -> Result<DocBlock, ParsingError> {
+		let mut parser = Self {
+			content: file,
+			chars: file.char_indices().peekable(),
+		};
+		while parser.chars.next_if(|&(pos, _)| pos < byte_offset).is_some() {}
+		let end_pos = parser.skip_to_tag("enddoc", false).unwrap_or(file.len());
+		let content = file.get(byte_offset..end_pos).ok_or(ParsingError::NoDocContentFound)?;
+		let (line, column) = parser.get_line_and_column(byte_offset);
+
+		Self::parse_doc_content_with_offset(content, byte_offset).map_err(|error| error.offset_position(line, column))
+	}
+
+	/// Parse doc block content that starts `offset` bytes into its file with a custom [ParserConfig]
+	pub fn parse_doc_content_with_config(
+		content: &'a str,
+		offset: usize,
+		config: &ParserConfig,
+	) -> Result<DocBlock, ParsingError> {
+		Self::parse_doc_content_recovering(content, offset, config, None, &mut Vec::new())
+			.map_err(|error| error.with_column_mode(content, config.column_mode))
+	}
+
+	/// Parse doc block content without stopping at broken `@param` lines
+	///
+	/// Returns whatever could be parsed together with every error and warning found along the way, which is what editor
+	/// tooling needs to show partial information for docs that are still being written. Error columns count bytes like
+	/// with the default [ParserConfig], see [ParserConfig::column_mode].
+	pub fn parse_doc_content_lenient(content: &'a str) -> (Option<DocBlock>, Vec<ParsingError>, Vec<ParseWarning>) {
+		let mut errors = Vec::new();
+		let mut warnings = Vec::new();
+		match Self::parse_doc_content_recovering(content, 0, &ParserConfig::default(), Some(&mut errors), &mut warnings) {
+			Ok(doc_block) => (Some(doc_block), errors, warnings),
+			Err(error) => {
+				// an empty block is only worth reporting when nothing else explains it
+				if errors.is_empty() {
+					errors.push(error);
+				}
+				(None, errors, warnings)
+			},
+		}
+	}
+
+	/// Helper function for the parse functions, `@param` errors are pushed to `recovered` if given instead of returned and
+	/// problems that don't fail the parse are pushed to `warnings`
+	fn parse_doc_content_recovering(
+		content: &'a str,
+		offset: usize,
+		config: &ParserConfig,
+		mut recovered: Option<&mut Vec<ParsingError>>,
+		warnings: &mut Vec<ParseWarning>,
+	) -> Result<DocBlock, ParsingError> {
 		let mut parser = Self {
 			content,
 			chars: content.char_indices().peekable(),
@@ -161,8 +688,12 @@ impl<'a> LiquidDocs<'a> {
 		while let Some((line_start, ch)) = parser.chars.next() {
 			// description without @description
 			if doc_block.description.is_empty() && ch != '@' {
-				let end_pos = parser.consume_until_either(&["@param", "@example", "@description"]).unwrap_or(content.len());
-				doc_block.description = String::from(content[line_start..end_pos].trim());
+				let end_pos = parser.consume_until_next_tag(line_start);
+				doc_block.description = Self::normalize_description(content[line_start..end_pos].trim());
+			} else if ch == '{' && Self::find_tag_in_line(&content[line_start..], "raw") == Some(0) {
+				// nothing inside a `{% raw %}` block is a tag
+				parser.skip_to_tag("endraw", true);
+				continue;
 			}
 
 			if ch == '@' {
@@ -173,178 +704,77 @@ impl<'a> LiquidDocs<'a> {
 					parser.consume_whitespace();
 
 					let start_pos = parser.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
-					let end_pos = parser.consume_until_either(&["@param", "@example", "@description"]).unwrap_or(content.len());
+					let end_pos = parser.consume_until_next_tag(start_pos);
 
 					if end_pos > start_pos {
 						if let Some(stripped) = content[start_pos..end_pos].trim().strip_prefix('-') {
-							doc_block.description = String::from(stripped.trim());
+							doc_block.description = Self::normalize_description(stripped.trim());
 						} else {
-							doc_block.description = String::from(content[start_pos..end_pos].trim());
+							doc_block.description = Self::normalize_description(content[start_pos..end_pos].trim());
 						}
 					}
 				}
 
-				// @param
-				if parser.peek_matches("param") {
-					parser.consume_chars(5);
+				// @param-group "Name" starts a group all following params belong to until the next group
+				if parser.peek_matches("param-group") {
+					parser.consume_chars(11);
 					parser.consume_whitespace_until_newline();
-					let mut param = Param::default();
-					let (start_pos, ch) = if let Some((pos, ch)) = parser.chars.peek() {
-						(*pos, *ch)
-					} else {
-						// peek yielded None so we're at the end of the string
-						let (line, column) = parser.get_line_and_column(content.len());
-						return Err(ParsingError::UnexpectedParameterEnd {
-							line,
-							column,
-							offending_line: parser.cut_till_newline(line_start),
-						});
-					};
-
-					// @param type (optional)
-					if ch == '{' {
-						parser.chars.next(); // consume '{'
-						if parser.chars.next().is_none() {
-							// next yielded None so we're at the end of the string
-							let (line, column) = parser.get_line_and_column(content.len());
-							return Err(ParsingError::UnexpectedParameterEnd {
-								line,
-								column,
-								offending_line: parser.cut_till_newline(line_start),
-							});
-						};
-
-						if let Some(end_pos) = parser.consume_until("}") {
-							let mut type_name = content[start_pos + 1..end_pos].trim();
-							let is_array = if type_name.ends_with("[]") {
-								type_name = &type_name[..type_name.len() - 2];
-								true
-							} else {
-								false
-							};
-
-							let explicit_type = if type_name == "string" {
-								ParamType::String
-							} else if type_name == "number" {
-								ParamType::Number
-							} else if type_name == "boolean" {
-								ParamType::Boolean
-							} else if type_name == "object" {
-								ParamType::Object
-							} else {
-								let is_valid_param_type = matches!(type_name, "string" | "number" | "boolean" | "object")
-									|| SHOPIFY_ALLOWED_OBJECTS.contains(&type_name);
-
-								if !is_valid_param_type {
-									let (line, column) = parser.get_line_and_column(start_pos);
-									return Err(ParsingError::UnknownParameterType {
-										line,
-										column,
-										offending_type: String::from(type_name),
-									});
-								} else {
-									ParamType::Shopify(String::from(type_name))
-								}
-							};
-
-							if is_array {
-								param.type_ = Some(ParamType::ArrayOf(Box::new(explicit_type)));
-							} else {
-								param.type_ = Some(explicit_type);
-							}
-						} else {
-							// consume_until yielded None so we're at the end of the string
-							let (line, column) = parser.get_line_and_column(content.len());
-							return Err(ParsingError::UnexpectedParameterEnd {
-								line,
-								column,
-								offending_line: parser.cut_till_newline(line_start),
-							});
-						}
 
-						parser.chars.next(); // consume '}'
-					}
+					let start_pos = parser.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
+					let end_pos = parser.consume_until("\n").unwrap_or(content.len());
+					let name = content[start_pos..end_pos].trim();
 
-					// @param optionality
-					parser.consume_whitespace_until_newline();
-					let (start_pos, optional) = if let Some((pos, ch)) = parser.chars.peek() {
-						if ch == &'[' { (*pos + 1, true) } else { (*pos, false) }
-					} else {
-						// peek yielded None so we're at the end of the string
-						// note: this code path is caught by UnexpectedParameterEnd above but we leave it here for completeness
-						let (line, column) = parser.get_line_and_column(content.len());
-						return Err(ParsingError::MissingParameterName {
-							line,
-							column,
-							offending_line: parser.cut_till_newline(line_start),
-						});
-					};
-					param.optional = optional;
-					if optional {
-						parser.chars.next(); // consume '['
-					}
+					doc_block.param_groups.push(ParamGroup {
+						name: String::from(name.strip_prefix('"').and_then(|name| name.strip_suffix('"')).unwrap_or(name)),
+						params: Vec::new(),
+					});
+				}
 
-					// @param name
+				// @param
+				if parser.peek_matches("param") {
+					parser.consume_chars(5);
 					parser.consume_whitespace_until_newline();
-					let current_column = parser.chars.peek().map(|&(c, _)| c).unwrap_or(line_start);
-					let end_pos = if optional {
-						match parser.consume_until("]") {
-							Some(index) => index,
-							None => {
-								let (line, column) = parser.get_line_and_column(current_column);
-								return Err(ParsingError::MissingOptionalClosingBracket {
-									line,
-									column,
-									offending_line: parser.cut_till_newline(line_start),
-								});
-							},
-						}
-					} else {
-						parser.consume_until_either(&[" ", "\n"]).unwrap_or(content.len())
+					let param = match (parser.parse_param(line_start, offset, config), recovered.as_deref_mut()) {
+						(Ok(param), _) => param.into_owned(),
+						(Err(error), Some(errors)) => {
+							// the error can leave the parser anywhere, even past the broken line, so resume right after it
+							errors.push(error);
+							let line_end = content[line_start..].find('\n').map_or(content.len(), |end| line_start + end);
+							parser.chars = content.char_indices().peekable();
+							while parser.chars.next_if(|&(pos, _)| pos < line_end).is_some() {}
+							continue;
+						},
+						(Err(error), None) => return Err(error),
 					};
-
-					param.name = String::from(content[start_pos..end_pos].trim());
-
-					if optional {
-						parser.chars.next(); // consume ']'
-					}
-
-					if param.name.is_empty() {
+					if config.warn_on_shadowing && Self::is_builtin_object(&param.name) {
 						let (line, _) = parser.get_line_and_column(line_start);
-						let (_, column) = parser.get_line_and_column(end_pos);
-						return Err(ParsingError::MissingParameterName {
+						warnings.push(ParseWarning::ParamShadowsBuiltin {
+							name: param.name.clone(),
 							line,
-							column,
-							offending_line: parser.cut_till_newline(line_start),
 						});
 					}
-					if param.name.contains('\n') {
-						let (line, column) = parser.get_line_and_column(current_column);
-						return Err(ParsingError::MissingOptionalClosingBracket {
-							line,
-							column,
-							offending_line: parser.cut_till_newline(line_start),
-						});
+					// only the first param that breaks the pattern of the first param is reported
+					if doc_block.param.first().is_some_and(|first| first.is_positional() != param.is_positional())
+						&& !warnings.iter().any(|warning| matches!(warning, ParseWarning::MixedPositionalParams { .. }))
+					{
+						let (line, _) = parser.get_line_and_column(line_start);
+						warnings.push(ParseWarning::MixedPositionalParams { line });
+					}
+					if let Some(group) = doc_block.param_groups.last_mut() {
+						group.params.push(param.clone());
 					}
+					doc_block.param.push(param);
 
-					// @param description (optional)
-					if let Some((_, ch)) = parser.chars.peek()
-						&& ch != &'\n'
+					if let Some(limit) = config.max_params
+						&& doc_block.param.len() > limit
 					{
-						parser.consume_whitespace_until_newline();
-						let start_pos = if let Some((pos, ch)) = parser.chars.peek() {
-							if ch == &'-' { *pos + 1 } else { *pos }
-						} else {
-							content.len()
-						};
-						let end_pos = parser.consume_until("\n").unwrap_or(content.len());
-						if end_pos > start_pos {
-							param.description = Some(String::from(content[start_pos..end_pos].trim()));
-						}
-					};
-
-					if param != Param::default() {
-						doc_block.param.push(param);
+						let (line, column) = parser.get_line_and_column(line_start);
+						return Err(ParsingError::TooManyParams {
+							line,
+							column,
+							count: doc_block.param.len(),
+							limit,
+						});
 					}
 				}
 
@@ -357,31 +787,43 @@ impl<'a> LiquidDocs<'a> {
 					} else {
 						content.len()
 					};
-					let end_pos = parser.consume_until_either(&["@param", "@example", "@description"]).unwrap_or(content.len());
-
-					let mut example = String::new();
-					let indentation_level = &content[start_pos..end_pos].chars().take_while(|c| c.is_whitespace()).count();
-					if *indentation_level > 0 {
-						content[start_pos..end_pos]
-							.trim()
-							.lines()
-							.map(|line| {
-								let chars_to_skip = line.chars().take(*indentation_level - 1).take_while(|c| c.is_whitespace()).count();
-								&line[line.char_indices().nth(chars_to_skip).map(|(i, _)| i).unwrap_or(line.len())..]
-							})
-							.enumerate()
-							.for_each(|(idx, stripped_line)| {
-								if idx > 0 {
-									example.push('\n');
-								}
-								example.push_str(stripped_line);
-							});
+					let end_pos = parser.consume_until_next_tag(start_pos);
+
+					let example = Self::dedent_example(&content[start_pos..end_pos], config.tab_width);
+
+					if example.is_empty() {
+						let (line, _) = parser.get_line_and_column(line_start);
+						warnings.push(ParseWarning::EmptyExample { line });
 					} else {
-						example = String::from(content[start_pos..end_pos].trim());
+						doc_block.example.push(ExampleBlock {
+							body: example,
+							span: Span {
+								start: offset + line_start,
+								end: offset + start_pos + content[start_pos..end_pos].trim_end().len(),
+							},
+						});
 					}
+				}
+
+				// @deprecated (optional) e.g. `@deprecated 2.0.0 Use 'new-snippet' instead. See https://...`
+				if parser.peek_matches("deprecated") && doc_block.deprecated.is_none() {
+					parser.consume_chars(10);
+					parser.consume_whitespace_until_newline();
+					let start_pos = parser.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
+					let end_pos = parser.consume_until_next_tag(start_pos);
 
-					if !example.is_empty() {
-						doc_block.example.push(example);
+					doc_block.deprecated = Some(Self::parse_deprecated(content[start_pos..end_pos].trim()));
+				}
+
+				// @link (optional) e.g. `@link https://example.com "Optional label"`
+				if parser.peek_matches("link") {
+					parser.consume_chars(4);
+					parser.consume_whitespace_until_newline();
+					let start_pos = parser.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
+					let end_pos = parser.consume_until("\n").unwrap_or(content.len());
+
+					if let Some(link) = Self::parse_link(content[start_pos..end_pos].trim()) {
+						doc_block.links.push(link);
 					}
 				}
 			}
@@ -394,38 +836,303 @@ impl<'a> LiquidDocs<'a> {
 		}
 	}
 
-	/// Move the cursor to the next non-whitespace character
-	fn consume_whitespace(&mut self) {
-		while self.chars.peek().map(|(_, ch)| ch.is_whitespace()).unwrap_or(false) {
-			self.chars.next();
-		}
-	}
+	/// Parse a single `@param` tag at the start of `content` without copying its name or description where possible
+	pub fn parse_param_ref(content: &'a str) -> Result<ParamRef<'a>, ParsingError> {
+		let mut parser = Self {
+			content,
+			chars: content.char_indices().peekable(),
+		};
 
-	/// Move the cursor to the next non-whitespace character unless it's a newline
-	fn consume_whitespace_until_newline(&mut self) {
-		while self.chars.peek().map(|(_, ch)| ch.is_whitespace() && ch != &'\n').unwrap_or(false) {
-			self.chars.next();
+		parser.consume_whitespace();
+		let line_start = parser.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
+		if parser.chars.next_if(|&(_, ch)| ch == '@').is_none()
+			|| !parser.peek_matches("param")
+			|| parser.peek_matches("param-group")
+		{
+			return Err(ParsingError::NoDocContentFound);
 		}
+		parser.consume_chars(5);
+		parser.consume_whitespace_until_newline();
+
+		parser.parse_param(line_start, 0, &ParserConfig::default())
 	}
 
-	/// Skip an optional dash character for whitespace control
-	fn skip_dash(&mut self) {
-		if self.chars.peek().map(|(_, ch)| *ch == '-').unwrap_or(false) {
-			self.chars.next();
-		}
+	/// Parse doc block content from any reader like a file or network stream
+	#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+	pub fn parse_doc_content_from_reader<R: std::io::Read>(mut reader: R) -> Result<DocBlock, ParsingError> {
+		let mut content = String::new();
+		reader.read_to_string(&mut content).map_err(|error| ParsingError::ReadError {
+			message: error.to_string(),
+		})?;
+
+		LiquidDocs::parse_doc_content(&content)
 	}
 
-	/// Check if the following content matches a specific substring
-	fn peek_matches(&mut self, needle: &str) -> bool {
-		self
-			.chars
-			.peek()
-			.map(|(start_pos, _)| {
-				let end_pos = start_pos + needle.len();
+	/// Parse a single `@param` line starting right after the `@param` keyword
+	fn parse_param(
+		&mut self,
+		line_start: usize,
+		offset: usize,
+		config: &ParserConfig,
+	) -> Result<ParamRef<'a>, ParsingError> {
+		let content = self.content;
+		let mut param = ParamRef::default();
+		let (start_pos, ch) = if let Some((pos, ch)) = self.chars.peek() {
+			(*pos, *ch)
+		} else {
+			// peek yielded None so we're at the end of the string
+			let (line, column) = self.get_line_and_column(content.len());
+			return Err(ParsingError::UnexpectedParameterEnd {
+				line,
+				column,
+				offending_line: self.cut_till_newline(line_start),
+			});
+		};
 
-				if end_pos <= self.content.len() && self.content[*start_pos..end_pos].eq_ignore_ascii_case(needle) {
-					if end_pos < self.content.len() {
-						// Safe because if the string comparison succeeds, end_pos must be on a char boundary
+		// @param type (optional)
+		if ch == '{' {
+			self.chars.next(); // consume '{'
+			if self.chars.peek().is_none() {
+				// peek yielded None so we're at the end of the string
+				let (line, column) = self.get_line_and_column(content.len());
+				return Err(ParsingError::UnexpectedParameterEnd {
+					line,
+					column,
+					offending_line: self.cut_till_newline(line_start),
+				});
+			};
+
+			if let Some(end_pos) = self.consume_until("}") {
+				let mut type_name = content[start_pos + 1..end_pos].trim();
+				// braces without a type are most likely a leftover while writing the doc so we treat the param as untyped
+				if !type_name.is_empty() {
+					let mut array_depth = 0;
+					while let Some(item_type) = type_name.strip_suffix("[]") {
+						type_name = item_type;
+						array_depth += 1;
+					}
+					if array_depth > config.max_array_depth {
+						let (line, column) = self.get_line_and_column(start_pos);
+						return Err(ParsingError::ArrayNestingTooDeep {
+							line,
+							column,
+							depth: array_depth,
+							max: config.max_array_depth,
+						});
+					}
+
+					let explicit_type = if type_name == "string" {
+						ParamType::String
+					} else if type_name == "number" {
+						ParamType::Number
+					} else if type_name == "boolean" {
+						ParamType::Boolean
+					} else if type_name == "object" {
+						ParamType::Object
+					} else if type_name == "any" {
+						ParamType::Any
+					} else if let Some(values) = type_name.strip_prefix("enum:").and_then(Self::parse_enum_values) {
+						ParamType::Enum(values)
+					} else if let Some(name) = type_name.strip_prefix("object:").and_then(Self::parse_quoted) {
+						if !config.named_object_schemas.iter().any(|schema| schema == name) {
+							let (line, column) = self.get_line_and_column(start_pos);
+							return Err(ParsingError::UnknownParameterType {
+								line,
+								column,
+								offending_type: String::from(type_name),
+							});
+						}
+						ParamType::NamedObject(String::from(name))
+					} else if let Some((base, constraints)) = type_name.split_once(':').and_then(|(base, constraints)| {
+						Some((ConstrainedBase::from_name(base.trim())?, Self::parse_constraints(constraints)?))
+					}) {
+						ParamType::Constrained { base, constraints }
+					} else {
+						let is_valid_param_type = PRIMITIVE_TYPES.contains(&type_name)
+							|| SHOPIFY_ALLOWED_OBJECTS.contains(&type_name)
+							|| SETTINGS_OBJECTS.contains(&type_name);
+
+						if !is_valid_param_type {
+							let (line, column) = self.get_line_and_column(start_pos);
+							return Err(ParsingError::UnknownParameterType {
+								line,
+								column,
+								offending_type: String::from(type_name),
+							});
+						} else {
+							ParamType::Shopify(String::from(type_name))
+						}
+					};
+
+					param.type_ =
+						Some((0..array_depth).fold(explicit_type, |item_type, _| ParamType::ArrayOf(Box::new(item_type))));
+				}
+			} else {
+				// consume_until yielded None so we're at the end of the string
+				let (line, column) = self.get_line_and_column(content.len());
+				return Err(ParsingError::UnexpectedParameterEnd {
+					line,
+					column,
+					offending_line: self.cut_till_newline(line_start),
+				});
+			}
+
+			self.chars.next(); // consume '}'
+		}
+
+		// @param optionality
+		self.consume_whitespace_until_newline();
+		let (start_pos, optional) = if let Some((pos, ch)) = self.chars.peek() {
+			if ch == &'[' { (*pos + 1, true) } else { (*pos, false) }
+		} else {
+			// peek yielded None so we're at the end of the string
+			// note: this code path is caught by UnexpectedParameterEnd above but we leave it here for completeness
+			let (line, column) = self.get_line_and_column(content.len());
+			return Err(ParsingError::MissingParameterName {
+				line,
+				column,
+				offending_line: self.cut_till_newline(line_start),
+			});
+		};
+		param.optional = optional;
+		if optional {
+			self.chars.next(); // consume '['
+		}
+
+		// @param name
+		self.consume_whitespace_until_newline();
+		let current_column = self.chars.peek().map(|&(c, _)| c).unwrap_or(line_start);
+		let end_pos = if optional {
+			match self.consume_until("]") {
+				Some(index) => index,
+				None => {
+					let (line, column) = self.get_line_and_column(current_column);
+					return Err(ParsingError::MissingOptionalClosingBracket {
+						line,
+						column,
+						offending_line: self.cut_till_newline(line_start),
+					});
+				},
+			}
+		} else {
+			self.consume_until_either(&[" ", "\n"]).unwrap_or(content.len())
+		};
+
+		param.name = Cow::Borrowed(content[start_pos..end_pos].trim());
+
+		if optional {
+			self.chars.next(); // consume ']'
+		}
+
+		if param.name.is_empty() {
+			let (line, _) = self.get_line_and_column(line_start);
+			let (_, column) = self.get_line_and_column(end_pos);
+			return Err(ParsingError::MissingParameterName {
+				line,
+				column,
+				offending_line: self.cut_till_newline(line_start),
+			});
+		}
+		if param.name.contains(['\n', '{']) {
+			let (line, column) = self.get_line_and_column(current_column);
+			let offending_line = self.cut_till_newline(line_start);
+			// a newline inside brackets means the closing bracket is missing, anywhere else the name itself is broken
+			return Err(if optional && param.name.contains('\n') {
+				ParsingError::MissingOptionalClosingBracket {
+					line,
+					column,
+					offending_line,
+				}
+			} else {
+				ParsingError::InvalidParameterName {
+					line,
+					column,
+					offending_line,
+				}
+			});
+		}
+
+		// @param description (optional)
+		if let Some((_, ch)) = self.chars.peek()
+			&& ch != &'\n'
+		{
+			self.consume_whitespace_until_newline();
+			let start_pos = if let Some((pos, ch)) = self.chars.peek() {
+				if ch == &'-' { *pos + 1 } else { *pos }
+			} else {
+				content.len()
+			};
+			let end_pos = self.consume_until("\n").unwrap_or(content.len());
+			if end_pos > start_pos && !content[start_pos..end_pos].trim().is_empty() {
+				param.description = Some(Cow::Borrowed(content[start_pos..end_pos].trim()));
+			}
+		};
+
+		// @param description continuation lines are indented deeper than the @param keyword itself
+		let param_indentation = content[..line_start].rsplit('\n').next().unwrap_or_default().chars().count();
+		while let Some((newline_pos, '\n')) = self.chars.peek().copied() {
+			let next_line = &content[newline_pos + 1..];
+			let next_line = &next_line[..next_line.find('\n').unwrap_or(next_line.len())];
+			let continuation = next_line.trim();
+
+			if continuation.is_empty()
+				|| continuation.starts_with('@')
+				|| next_line.chars().take_while(|c| c.is_whitespace()).count() <= param_indentation
+			{
+				break;
+			}
+
+			let description = param.description.get_or_insert_with(Cow::default).to_mut();
+			if !description.is_empty() {
+				description.push('\n');
+			}
+			description.push_str(continuation);
+
+			self.chars.next(); // consume '\n'
+			self.consume_until("\n");
+		}
+
+		let end_pos = self.chars.peek().map(|(pos, _)| *pos).unwrap_or(content.len());
+		param.span = Some(Span {
+			start: offset + line_start,
+			end: offset + line_start + content[line_start..end_pos].trim_end().len(),
+		});
+
+		Ok(param)
+	}
+
+	/// Move the cursor to the next non-whitespace character
+	fn consume_whitespace(&mut self) {
+		while self.chars.peek().map(|(_, ch)| ch.is_whitespace()).unwrap_or(false) {
+			self.chars.next();
+		}
+	}
+
+	/// Move the cursor to the next non-whitespace character unless it's a newline
+	fn consume_whitespace_until_newline(&mut self) {
+		while self.chars.peek().map(|(_, ch)| ch.is_whitespace() && ch != &'\n').unwrap_or(false) {
+			self.chars.next();
+		}
+	}
+
+	/// Skip an optional dash character for whitespace control
+	fn skip_dash(&mut self) {
+		if self.chars.peek().map(|(_, ch)| *ch == '-').unwrap_or(false) {
+			self.chars.next();
+		}
+	}
+
+	/// Check if the following content matches a specific substring
+	fn peek_matches(&mut self, needle: &str) -> bool {
+		self
+			.chars
+			.peek()
+			.map(|(start_pos, _)| {
+				let end_pos = start_pos + needle.len();
+
+				if self.content.get(*start_pos..end_pos).is_some_and(|found| found.eq_ignore_ascii_case(needle)) {
+					if end_pos < self.content.len() {
+						// Safe because if the string comparison succeeds, end_pos must be on a char boundary
 						let next_byte = self.content.as_bytes()[end_pos];
 						!next_byte.is_ascii_alphanumeric()
 					} else {
@@ -479,10 +1186,7 @@ impl<'a> LiquidDocs<'a> {
 		let target_len = target.len();
 
 		while let Some((pos, ch)) = self.chars.peek() {
-			if *ch == first_char
-				&& *pos + target_len <= self.content.len()
-				&& self.content[*pos..*pos + target_len] == *target
-			{
+			if *ch == first_char && self.content.get(*pos..*pos + target_len) == Some(target) {
 				return Some(*pos);
 			}
 			self.chars.next();
@@ -505,6 +1209,32 @@ impl<'a> LiquidDocs<'a> {
 		None
 	}
 
+	/// Consume until the next tag that isn't inside a `{% raw %}` block or `{% liquid %}` tag opened after `start_pos`
+	/// and return its position or the end of the content
+	fn consume_until_next_tag(&mut self, start_pos: usize) -> usize {
+		loop {
+			let Some(end_pos) = self.consume_until_either(&TAGS) else {
+				return self.content.len();
+			};
+			let code = &self.content[start_pos..end_pos];
+			if Self::is_whole_tag(&self.content[end_pos..])
+				&& !Self::is_inside_raw_block(code)
+				&& !Self::is_inside_liquid_tag(code)
+			{
+				return end_pos;
+			}
+			self.chars.next(); // consume '@'
+		}
+	}
+
+	/// Check that the tag at the start of `content` is followed by whitespace, a type or the end so words that only start
+	/// like a tag e.g. `@linkedin` or `user@deprecated.example` stay part of the text
+	fn is_whole_tag(content: &str) -> bool {
+		TAGS.iter().any(|tag| {
+			content.strip_prefix(tag).is_some_and(|rest| rest.chars().next().is_none_or(|ch| ch.is_whitespace() || ch == '{'))
+		})
+	}
+
 	/// Find the next given tag in the input stream and either return the position before or after the closing tag
 	fn skip_to_tag(&mut self, tag: &str, return_end: bool) -> Option<usize> {
 		while let Some(tag_start) = self.consume_until("{%") {
@@ -528,28 +1258,284 @@ impl<'a> LiquidDocs<'a> {
 		None
 	}
 
-	/// Get the line and column (1 indexed) of a given byte offset in the input stream
-	fn get_line_and_column(&self, byte_offset: usize) -> (usize, usize) {
-		let bytes = self.content.as_bytes();
-		let mut line = 1;
-		let mut last_newline_pos = 0;
-
-		for (i, byte) in bytes.iter().enumerate().take(byte_offset.min(bytes.len())) {
-			if *byte == b'\n' {
-				line += 1;
-				last_newline_pos = i + 1;
+	/// Collapse whitespace-only lines so paragraphs within a description are always separated by an empty line
+	fn normalize_description(description: &str) -> String {
+		description.lines().map(|line| if line.trim().is_empty() { "" } else { line }).collect::<Vec<_>>().join("\n")
+	}
+
+	/// Split a deprecation notice into its leading version, its message, a quoted replacement and a trailing link
+	fn parse_deprecated(notice: &str) -> DeprecatedInfo {
+		let mut message = notice;
+
+		let first_word = message.split_whitespace().next().unwrap_or_default();
+		let version_number = first_word.strip_prefix('v').unwrap_or(first_word);
+		let version = (version_number.starts_with(|c: char| c.is_ascii_digit())
+			&& version_number.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())))
+		.then(|| {
+			message = message[first_word.len()..].trim_start();
+			String::from(first_word)
+		});
+
+		let last_word = message.split_whitespace().last().unwrap_or_default();
+		let link = (last_word.starts_with("https://") || last_word.starts_with("http://")).then(|| {
+			message = message[..message.len() - last_word.len()].trim_end();
+			message = message.strip_suffix("See").map(str::trim_end).unwrap_or(message);
+			String::from(last_word.trim_end_matches(['.', ')']))
+		});
+
+		let replacement = message.find("Use ").and_then(|index| {
+			let quoted = &message[index + 4..];
+			let quote = quoted.chars().next().filter(|quote| matches!(quote, '"' | '\'' | '`'))?;
+			quoted[1..].find(quote).map(|end| String::from(&quoted[1..end + 1]))
+		});
+
+		DeprecatedInfo {
+			message: String::from(message),
+			version,
+			replacement,
+			link,
+		}
+	}
+
+	/// Parse the URL and optional quoted label of an `@link` tag, links without a URL are dropped
+	fn parse_link(link: &str) -> Option<DocLink> {
+		let (url, label) = link.split_once(char::is_whitespace).unwrap_or((link, ""));
+		if url.is_empty() {
+			return None;
+		}
+
+		let label = label.trim();
+		let label = label
+			.strip_prefix('"')
+			.and_then(|label| label.strip_suffix('"'))
+			.or_else(|| label.strip_prefix('\'').and_then(|label| label.strip_suffix('\'')))
+			.unwrap_or(label);
+
+		Some(DocLink {
+			url: String::from(url),
+			label: (!label.is_empty()).then(|| String::from(label)),
+		})
+	}
+
+	/// Parse the values of an enum type like `"left"|'right'`, every value has to be quoted
+	fn parse_enum_values(values: &str) -> Option<Vec<String>> {
+		values.split('|').map(|value| Self::parse_quoted(value).map(String::from)).collect()
+	}
+
+	/// Parse the comma separated `key=value` constraints of a type like `min=1, max=100`
+	fn parse_constraints(constraints: &str) -> Option<BTreeMap<String, String>> {
+		constraints
+			.split(',')
+			.map(|constraint| {
+				let (key, value) = constraint.split_once('=')?;
+				let (key, value) = (key.trim(), value.trim());
+				(!key.is_empty() && !value.is_empty()).then(|| (String::from(key), String::from(value)))
+			})
+			.collect()
+	}
+
+	/// Strip the surrounding whitespace and single or double quotes of a value like ` "article-card" `
+	fn parse_quoted(value: &str) -> Option<&str> {
+		let value = value.trim();
+		let quote = value.chars().next().filter(|quote| matches!(quote, '"' | '\''))?;
+		value[1..].strip_suffix(quote)
+	}
+
+	/// Strip the indentation all lines of an example share, like Python's `textwrap.dedent`, while keeping the content of
+	/// {% raw %} blocks verbatim
+	fn dedent_example(body: &str, tab_width: usize) -> String {
+		// drop leading blank lines but keep the indentation of the first line with content
+		let body = body.trim_end();
+		let leading_whitespace = &body[..body.len() - body.trim_start().len()];
+		let body = &body[leading_whitespace.rfind('\n').map(|pos| pos + 1).unwrap_or(0)..];
+
+		let mut in_raw = false;
+		let lines = body
+			.lines()
+			.map(|line| {
+				let is_raw_content = if in_raw {
+					in_raw = Self::find_tag_in_line(line, "endraw").is_none();
+					in_raw
+				} else {
+					if let Some(raw_start) = Self::find_tag_in_line(line, "raw") {
+						in_raw = Self::find_tag_in_line(&line[raw_start..], "endraw").is_none();
+					}
+					false
+				};
+				(line, is_raw_content)
+			})
+			.collect::<Vec<_>>();
+
+		let min_indentation = lines
+			.iter()
+			.filter(|(line, is_raw_content)| !is_raw_content && !line.trim().is_empty())
+			.map(|(line, _)| Self::indentation_width(line, tab_width))
+			.min()
+			.unwrap_or(0);
+
+		let mut example = String::with_capacity(body.len());
+		for (idx, (line, is_raw_content)) in lines.into_iter().enumerate() {
+			if idx > 0 {
+				example.push('\n');
+			}
+
+			if is_raw_content {
+				example.push_str(line);
+			} else {
+				let mut width = 0;
+				let mut content_start = line.len();
+				for (pos, c) in line.char_indices() {
+					let char_width = if c == '\t' { tab_width } else { 1 };
+					if !c.is_whitespace() || width == min_indentation {
+						content_start = pos;
+						break;
+					}
+					if width + char_width > min_indentation {
+						// a tab that reaches past the common indentation keeps its remaining columns as spaces
+						example.extend(core::iter::repeat_n(' ', width + char_width - min_indentation));
+						content_start = pos + c.len_utf8();
+						break;
+					}
+					width += char_width;
+				}
+				example.push_str(&line[content_start..]);
+			}
+		}
+
+		example
+	}
+
+	/// Check that every `{% %}` and `{{ }}` in an example is closed before the next one opens and return what's wrong
+	fn check_example_syntax(example: &str) -> Option<(usize, String)> {
+		let mut pos = 0;
+
+		while let Some((start, token)) = ["{%", "{{", "%}", "}}"]
+			.into_iter()
+			.filter_map(|token| example[pos..].find(token).map(|index| (pos + index, token)))
+			.min()
+		{
+			let (line, _) = line_and_column(example, start);
+			let close = match token {
+				"{%" => "%}",
+				"{{" => "}}",
+				_ => return Some((line, format!("`{token}` without an opening tag"))),
+			};
+
+			let inner = &example[start + 2..];
+			let end = inner.find(close);
+			let next_open = ["{%", "{{"].into_iter().filter_map(|token| inner.find(token)).min();
+			match end {
+				Some(end) if next_open.is_none_or(|next_open| end < next_open) => pos = start + 2 + end + 2,
+				_ => return Some((line, format!("`{token}` is never closed with `{close}`"))),
+			}
+		}
+
+		None
+	}
+
+	/// Find the byte position of an opening liquid tag like `{%- raw %}` within a single line
+	fn find_tag_in_line(line: &str, tag: &str) -> Option<usize> {
+		let mut offset = 0;
+
+		while let Some(pos) = line[offset..].find("{%") {
+			let tag_start = offset + pos;
+			let rest = line[tag_start + 2..].trim_start_matches('-').trim_start();
+
+			if rest.get(..tag.len()).is_some_and(|name| name.eq_ignore_ascii_case(tag))
+				&& !rest[tag.len()..].starts_with(|c: char| c.is_ascii_alphanumeric())
+			{
+				return Some(tag_start);
 			}
+
+			offset = tag_start + 2;
+		}
+
+		None
+	}
+
+	/// Check if the end of the given code is inside a `{% liquid %}` tag that hasn't been closed yet
+	fn is_inside_liquid_tag(code: &str) -> bool {
+		// liquid tags can't contain other tags so only the last opening tag matters
+		code.rfind("{%").is_some_and(|tag_start| {
+			Self::find_tag_in_line(&code[tag_start..], "liquid") == Some(0) && !code[tag_start..].contains("%}")
+		})
+	}
+
+	/// Check if the code starts with the given opening HTML tag e.g. `<script type="application/json">`
+	fn starts_with_html_tag(code: &str, tag: &str) -> bool {
+		code.get(1..=tag.len()).is_some_and(|name| name.eq_ignore_ascii_case(tag))
+			&& code[tag.len() + 1..].starts_with(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+	}
+
+	/// Find the position of the closing HTML tag e.g. `</script>`
+	fn find_closing_html_tag(code: &str, tag: &str) -> Option<usize> {
+		code
+			.match_indices("</")
+			.map(|(pos, _)| pos)
+			.find(|&pos| code.get(pos + 2..pos + 2 + tag.len()).is_some_and(|name| name.eq_ignore_ascii_case(tag)))
+	}
+
+	/// Check if a name belongs to a Shopify object type or a global object that every template can access
+	fn is_builtin_object(name: &str) -> bool {
+		SHOPIFY_ALLOWED_OBJECTS.contains(&name) || LIQUID_GLOBALS.contains(&name)
+	}
+
+	/// The dedented content of a `{% raw %}` block that wraps the whole example body
+	pub(crate) fn unwrap_raw_example(body: &str) -> Option<String> {
+		let body = body.trim();
+		if Self::find_tag_in_line(body, "raw") != Some(0) || !body.ends_with("%}") {
+			return None;
+		}
+
+		let inner_start = body.find("%}")? + 2;
+		let inner_end = body.rfind("{%")?;
+		if inner_end < inner_start || Self::find_tag_in_line(&body[inner_end..], "endraw") != Some(0) {
+			return None;
+		}
+
+		// an earlier endraw means the body holds more than a single raw block
+		let inner = &body[inner_start..inner_end];
+		if Self::find_tag_in_line(inner, "endraw").is_some() {
+			return None;
+		}
+
+		Some(Self::dedent_example(inner, ParserConfig::default().tab_width))
+	}
+
+	/// The visual width of the leading whitespace of a line with each tab counting as `tab_width` columns
+	fn indentation_width(line: &str, tab_width: usize) -> usize {
+		line.chars().take_while(|c| c.is_whitespace()).map(|c| if c == '\t' { tab_width } else { 1 }).sum()
+	}
+
+	/// Check if the end of the given code is inside a `{% raw %}` block that hasn't been closed yet
+	fn is_inside_raw_block(code: &str) -> bool {
+		let mut inside = false;
+		let mut rest = code;
+		while let Some(tag_start) = Self::find_tag_in_line(rest, if inside { "endraw" } else { "raw" }) {
+			inside = !inside;
+			rest = &rest[tag_start + 2..];
 		}
+		inside
+	}
 
-		let column = byte_offset - last_newline_pos + 1;
-		(line, column)
+	/// Get the line and column (1 indexed) of a given byte offset in the input stream
+	fn get_line_and_column(&self, byte_offset: usize) -> (usize, usize) {
+		line_and_column(self.content, byte_offset)
 	}
 
 	/// Allocate a new String from line_start till next newline character
 	fn cut_till_newline(&self, line_start: usize) -> String {
-		match self.content[line_start..].find('\n') {
-			Some(newline_pos) => String::from(&self.content[line_start..line_start + newline_pos]),
-			None => String::from(&self.content[line_start..]),
+		let line = match self.content[line_start..].find('\n') {
+			Some(newline_pos) => &self.content[line_start..line_start + newline_pos],
+			None => &self.content[line_start..],
+		};
+
+		// minified or broken files can have megabytes on a single line so we only quote its start
+		if line.len() > MAX_OFFENDING_LINE_BYTES {
+			let end = (0..=MAX_OFFENDING_LINE_BYTES).rev().find(|&end| line.is_char_boundary(end)).unwrap_or(0);
+			format!("{}…", &line[..end])
+		} else {
+			String::from(line)
 		}
 	}
 }
@@ -557,6 +1543,8 @@ impl<'a> LiquidDocs<'a> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::Param;
+	use alloc::vec;
 
 	#[test]
 	fn extract_doc_blocks_test() {
@@ -579,12 +1567,52 @@ mod tests {
 			Some(vec!["  test "])
 		);
 		assert_eq!(LiquidDocs::extract_doc_blocks("{%doc%}test{%enddoc%}test"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc extra-content %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc-extra %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% docs %}test{% enddoc %}"), None);
 		assert_eq!(LiquidDocs::extract_doc_blocks("{% raw %}{% doc %}test{% enddoc %}{% endraw %}test"), None);
 		assert_eq!(LiquidDocs::extract_doc_blocks("{% raw %}{% doc %}test{% enddoc %}{% endraw %}test"), None);
 		assert_eq!(LiquidDocs::extract_doc_blocks("test {% # {% doc %}test{% enddoc %} test"), None);
 		assert_eq!(LiquidDocs::extract_doc_blocks("test {% doc %} test"), None);
 		assert_eq!(LiquidDocs::extract_doc_blocks("{% comment %}{% doc %}test{% enddoc %}{% endcomment %}test"), None);
+		// comments aren't counted as nested, the first endcomment ends the outer comment as well
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks(
+				"{% comment %}{% comment %}{% doc %}test{% enddoc %}{% endcomment %}{% endcomment %}test"
+			),
+			None
+		);
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks(
+				"{% comment %}{% comment %}{% endcomment %}{% doc %}real{% enddoc %}{% endcomment %}"
+			),
+			Some(vec!["real"])
+		);
 		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc %}{% enddoc %}"), Some(vec![""]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{# enddoc #}{% doc %}real{% enddoc %}"), Some(vec!["real"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{# {% doc %}test{% enddoc %} #}test"), None);
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks("<p>{# todo</p>\n{% doc %}@param {string} x - y{% enddoc %}"),
+			Some(vec!["@param {string} x - y"])
+		);
+		// the first byte of a multi-byte character can't be the start of the target
+		assert_eq!(LiquidDocs::extract_doc_blocks("{#é #}{% doc %}x{% enddoc %}"), Some(vec!["x"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{# #é #}{% doc %}x{% enddoc %}"), Some(vec!["x"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc %}{{é{% enddoc %}"), Some(vec!["{{é"]));
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks("{# doc #}{% doc %}real{% enddoc %}{# {% enddoc %} #}"),
+			Some(vec!["real"])
+		);
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks(
+				"{% liquid\n  # @param {string} title\n  echo '{% doc %}test{% enddoc %}'\n%}test"
+			),
+			None
+		);
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks("{%- liquid\n  assign title = 'doc'\n-%}{% doc %}test{% enddoc %}"),
+			Some(vec!["test"])
+		);
 
 		let doc = r#"
   Provides an example of a snippet description.
@@ -627,13 +1655,156 @@ mod tests {
 		assert_eq!(LiquidDocs::extract_doc_blocks(&content), Some(vec![doc]));
 	}
 
+	#[test]
+	fn parse_test() {
+		let result = LiquidDocs::parse("{% doc %}@param foo{% enddoc %}<p></p>{% doc %}@param {unknown} bar{% enddoc %}");
+		assert_eq!(result.success.len(), 1);
+		assert_eq!(result.success[0].param[0].name, "foo");
+		assert_eq!(result.errors.len(), 1);
+		assert!(LiquidDocs::parse("<p>No docs</p>").success.is_empty());
+	}
+
+	#[test]
+	fn parse_param_shadows_builtin_test() {
+		let content = "{% doc %}\n@param {product} product\n@param cart\n@param title\n{% enddoc %}";
+		let result = LiquidDocs::parse(content);
+		assert_eq!(result.success[0].param.len(), 3);
+		assert_eq!(
+			result.warnings.iter().map(|warning| (warning.line, warning.message.as_str())).collect::<Vec<_>>(),
+			vec![
+				(2, "Param \"product\" on line 2 shadows the built-in Liquid object of the same name"),
+				(3, "Param \"cart\" on line 3 shadows the built-in Liquid object of the same name"),
+			]
+		);
+
+		let config = ParserConfig {
+			warn_on_shadowing: false,
+			..Default::default()
+		};
+		assert!(LiquidDocs::parse_with_config(content, &config).warnings.is_empty());
+	}
+
+	#[test]
+	fn liquid_globals_test() {
+		for name in LIQUID_GLOBALS {
+			assert!(!SHOPIFY_ALLOWED_OBJECTS.contains(&name), "{name}");
+		}
+		assert!(LiquidDocs::is_builtin_object("customer"));
+		assert!(LiquidDocs::is_builtin_object("localization"));
+		assert!(LiquidDocs::is_builtin_object("recommendations"));
+		assert!(!LiquidDocs::is_builtin_object("title"));
+	}
+
+	#[test]
+	fn parse_empty_example_test() {
+		let result = LiquidDocs::parse("{% doc %}\nRenders a card\n@example\n@param title\n{% enddoc %}");
+		assert!(result.success[0].example.is_empty());
+		assert_eq!(result.success[0].param[0].name, "title");
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].line, 3);
+		assert_eq!(result.warnings[0].message, ParseWarning::EmptyExample { line: 3 }.to_string());
+
+		let result = LiquidDocs::parse("{% doc %}\nRenders a card\n@example\n{% enddoc %}");
+		assert!(result.success[0].example.is_empty());
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].message, "Empty @example on line 3");
+
+		let result = LiquidDocs::parse("{% doc %}\nRenders a card\n@example\n{% render 'card' %}\n{% enddoc %}");
+		assert_eq!(result.success[0].example.len(), 1);
+		assert!(result.warnings.is_empty());
+	}
+
+	#[test]
+	fn extract_doc_blocks_without_doc_tag_test() {
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% comment %}enddoc{% endcomment %}"), None);
+		assert_eq!(LiquidDocs::extract_doc_blocks("{{ 'enddoc' }}"), None);
+		assert_eq!(LiquidDocs::extract_doc_blocks("{%doc%}test{%enddoc%}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{%-\n  doc %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% DOC %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% Doc %}test{% enddoc %}"), Some(vec!["test"]));
+	}
+
+	#[test]
+	fn extract_doc_blocks_whitespace_control_test() {
+		// everything between `doc` and `%}` is ignored, including misplaced dashes
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc- %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc - %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc -- %}test{% enddoc %}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{%-doc-%}test{%-enddoc-%}"), Some(vec!["test"]));
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc %}test{% enddoc- %}"), Some(vec!["test"]));
+		// a dash after whitespace is part of the tag name
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% - doc %}test{% enddoc %}"), None);
+		assert_eq!(LiquidDocs::extract_doc_blocks("{%- -doc %}test{% enddoc %}"), None);
+		// `%-}` doesn't close the tag
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc %-}test{% enddoc %}"), None);
+	}
+
+	#[test]
+	fn extract_doc_blocks_trim_dash_test() {
+		// whitespace control doesn't trim the content, it's the doc content parser that ignores surrounding whitespace
+		assert_eq!(LiquidDocs::extract_doc_blocks("{% doc -%}\n  test\n{%- enddoc %}"), Some(vec!["\n  test\n"]));
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks_with_spans("{%- doc -%}\n  test{% enddoc %}"),
+			Some(vec![(Span { start: 11, end: 18 }, "\n  test")])
+		);
+
+		let trimmed = LiquidDocs::parse("{%- doc -%}\n  Renders a card\n  @param {string} title\n{%- enddoc -%}");
+		let untrimmed = LiquidDocs::parse("{% doc %}\n  Renders a card\n  @param {string} title\n{% enddoc %}");
+		assert_eq!(trimmed.success[0].description, "Renders a card");
+		assert_eq!(trimmed.success[0].description, untrimmed.success[0].description);
+		assert_eq!(trimmed.success[0].param[0].name, untrimmed.success[0].param[0].name);
+	}
+
+	#[test]
+	fn extract_doc_blocks_with_spans_test() {
+		assert_eq!(LiquidDocs::extract_doc_blocks_with_spans("test"), None);
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks_with_spans("{% doc %}foo{% enddoc %}\n{%- doc -%}bar{% enddoc %}"),
+			Some(vec![
+				(Span { start: 9, end: 12 }, "foo"),
+				(Span { start: 36, end: 39 }, "bar")
+			])
+		);
+	}
+
+	#[test]
+	fn extract_doc_blocks_script_test() {
+		let content = r#"<script type="application/json">{"snippet": "{% doc %}@param foo{% enddoc %}"}</script>"#;
+		assert_eq!(LiquidDocs::extract_doc_blocks_with_spans(content), None);
+
+		let content = "<SCRIPT>const doc = '{% doc %}';</SCRIPT>\n<scripts>{% doc %}foo{% enddoc %}</scripts>";
+		assert_eq!(LiquidDocs::extract_doc_blocks_with_spans(content), Some(vec![(Span { start: 60, end: 63 }, "foo")]));
+
+		// an unclosed script doesn't hide the rest of the file
+		assert_eq!(
+			LiquidDocs::extract_doc_blocks_with_spans("<script>\n{% doc %}foo{% enddoc %}"),
+			Some(vec![(Span { start: 18, end: 21 }, "foo")])
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_with_offset_test() {
+		let content =
+			"<p>Hi</p>\n{% doc %}\n  @param {string} title - The title\n  @example\n  {% render 'card' %}\n{% enddoc %}";
+		let (span, block) = LiquidDocs::extract_doc_blocks_with_spans(content).unwrap().remove(0);
+		let doc_block = LiquidDocs::parse_doc_content_with_offset(block, span.start).unwrap();
+
+		let param_span = doc_block.param[0].span.unwrap();
+		assert_eq!(&content[param_span.start..param_span.end], "@param {string} title - The title");
+		let example_span = doc_block.example[0].span;
+		assert_eq!(&content[example_span.start..example_span.end], "@example\n  {% render 'card' %}");
+	}
+
 	#[test]
 	fn parse_doc_content_description_test() {
 		assert_eq!(
 			LiquidDocs::parse_doc_content("test"),
 			Ok(DocBlock {
 				description: String::from("test"),
+				deprecated: None,
 				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
@@ -654,7 +1825,10 @@ end
 			),
 			Ok(DocBlock {
 				description: String::from("The description 1\n\t\t\tWith new lines\n\t\tand different indentation\nend"),
+				deprecated: None,
 				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
@@ -670,7 +1844,10 @@ end
 			),
 			Ok(DocBlock {
 				description: String::from("The description 2\nalso with new lines\n  and some indentation\nend"),
+				deprecated: None,
 				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
@@ -679,24 +1856,117 @@ end
 			LiquidDocs::parse_doc_content("@description - The description 3"),
 			Ok(DocBlock {
 				description: String::from("The description 3"),
+				deprecated: None,
 				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
 	}
 
 	#[test]
-	fn parse_doc_content_param_complex_test() {
+	fn parse_doc_content_tag_prefix_test() {
+		// words that only start like a tag are part of the text
+		let result =
+			LiquidDocs::parse_doc_content("Follow us on @linkedin for updates\n@param {string} foo - bar").unwrap();
+		assert_eq!(result.description, "Follow us on @linkedin for updates");
+		assert_eq!(result.param[0].name, "foo");
+
+		let result =
+			LiquidDocs::parse_doc_content("@description Ask user@deprecated.example\n@deprecated Use 'card' instead.")
+				.unwrap();
+		assert_eq!(result.description, "Ask user@deprecated.example");
+		assert_eq!(result.deprecated.unwrap().message, "Use 'card' instead.");
+
+		let result = LiquidDocs::parse_doc_content("@example\n{{ '@linkedin' }}\n@param{string} foo").unwrap();
+		assert_eq!(result.first_example(), Some("{{ '@linkedin' }}"));
+		assert_eq!(result.param[0].type_, Some(ParamType::String));
+	}
+
+	#[test]
+	fn parse_doc_content_empty_description_test() {
+		let result = LiquidDocs::parse_doc_content("@description \n@param {string} foo - bar").unwrap();
+		assert_eq!(result.description, "");
+		assert_eq!(result.param.len(), 1);
+		assert_eq!(result.param[0].name, "foo");
+		assert_eq!(result.param[0].description, Some(String::from("bar")));
+
+		// an empty tag doesn't block a later description just like an absent one
+		let result = LiquidDocs::parse_doc_content("@description\t\n@param foo\n@description Card").unwrap();
+		assert_eq!(result.description, "Card");
+	}
+
+	#[test]
+	fn parse_doc_content_description_paragraphs_test() {
 		assert_eq!(
 			LiquidDocs::parse_doc_content(
-				r#"
-Description with words @ foobar
-end!
-
-@param {string}  [var1] - Optional variable 1
-		@param {  number  }  var2   - Variable 2
-                                  with new line
-end
+				"First paragraph\nstill first\n\nSecond paragraph\n  \t\nThird paragraph\n\n@param foo"
+			),
+			Ok(DocBlock {
+				description: String::from("First paragraph\nstill first\n\nSecond paragraph\n\nThird paragraph"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: None,
+					type_: None,
+					optional: false,
+					span: Some(Span { start: 67, end: 77 }),
+				}],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@description First paragraph\n\n\nSecond paragraph\n\n@example\nfoo"),
+			Ok(DocBlock {
+				description: String::from("First paragraph\n\n\nSecond paragraph"),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("foo"),
+					span: Span { start: 49, end: 61 }
+				}]
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_raw_block_test() {
+		let content = "Description\n{%- raw -%}\n@param {string} hidden\n{% endraw %}\n@param {string} shown";
+		let result = LiquidDocs::parse_doc_content(content).unwrap();
+		assert_eq!(result.description, "Description\n{%- raw -%}\n@param {string} hidden\n{% endraw %}");
+		assert_eq!(result.param.len(), 1);
+		assert_eq!(result.param[0].name, "shown");
+
+		let content = "@param {string} a\n{% raw %}\n@param {string} hidden\n@example\n{% endraw %}\n@param b";
+		let result = LiquidDocs::parse_doc_content(content).unwrap();
+		assert_eq!(result.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+		assert!(result.example.is_empty());
+
+		let content = "@example\n{% raw %}\n  @param {string} hidden\n{% endraw %}\n@param shown";
+		let result = LiquidDocs::parse_doc_content(content).unwrap();
+		assert_eq!(result.param.len(), 1);
+		assert_eq!(result.param[0].name, "shown");
+		assert_eq!(result.example[0].body, "{% raw %}\n  @param {string} hidden\n{% endraw %}");
+	}
+
+	#[test]
+	fn parse_doc_content_param_complex_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content(
+				r#"
+Description with words @ foobar
+end!
+
+@param {string}  [var1] - Optional variable 1
+		@param {  number  }  var2   - Variable 2
+                                  with new line
+end
 @example
 {% render 'example-snippet', var1: 'Featured Products', var2: 3, var5: {} %}
   @param {boolean} [ var3  ]   - Variable 3
@@ -712,41 +1982,55 @@ end
 			),
 			Ok(DocBlock {
 				description: String::from("Description with words @ foobar\nend!"),
+				deprecated: None,
 				param: vec![
 					Param {
 						name: String::from("var1"),
 						description: Some(String::from("Optional variable 1")),
 						type_: Some(ParamType::String),
 						optional: true,
+						span: Some(Span { start: 39, end: 84 }),
 					},
 					Param {
 						name: String::from("var2"),
-						description: Some(String::from("Variable 2")),
+						description: Some(String::from("Variable 2\nwith new line")),
 						type_: Some(ParamType::Number),
 						optional: false,
+						span: Some(Span { start: 87, end: 175 }),
 					},
 					Param {
 						name: String::from("var3"),
 						description: Some(String::from("Variable 3")),
 						type_: Some(ParamType::Boolean),
 						optional: true,
+						span: Some(Span { start: 268, end: 309 }),
 					},
 					Param {
 						name: String::from("var5"),
 						description: Some(String::from("Variable 5")),
 						type_: Some(ParamType::Object),
 						optional: false,
+						span: Some(Span { start: 316, end: 347 }),
 					},
 					Param {
 						name: String::from("var6"),
 						description: None,
 						type_: None,
 						optional: false,
+						span: Some(Span { start: 350, end: 361 }),
 					},
 				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: vec![
-					String::from("{% render 'example-snippet', var1: 'Featured Products', var2: 3, var5: {} %}"),
-					String::from("{% render 'example-snippet',\n  var1: variant.price,\n  var5: false\n%}")
+					ExampleBlock {
+						body: String::from("{% render 'example-snippet', var1: 'Featured Products', var2: 3, var5: {} %}"),
+						span: Span { start: 180, end: 265 }
+					},
+					ExampleBlock {
+						body: String::from("{% render 'example-snippet',\n  var1: variant.price,\n  var5: false\n%}"),
+						span: Span { start: 363, end: 440 }
+					}
 				]
 			})
 		);
@@ -776,35 +2060,43 @@ end
 			),
 			Ok(DocBlock {
 				description: String::from("Intended for use"),
+				deprecated: None,
 				param: vec![
 					Param {
 						name: String::from("link"),
 						description: Some(String::from("link to render")),
 						type_: Some(ParamType::String),
 						optional: false,
-					},
-					Param {
-						name: String::from("asdasd"),
-						description: None,
-						type_: None,
-						optional: false,
+						span: Some(Span { start: 104, end: 141 }),
 					},
 					Param {
 						name: String::from("block"),
 						description: Some(String::from("The block @param things and what not")),
 						type_: Some(ParamType::Object),
 						optional: true,
+						span: Some(Span { start: 266, end: 338 }),
 					},
 					Param {
 						name: String::from("foo"),
 						description: None,
 						type_: None,
 						optional: true,
+						span: Some(Span { start: 341, end: 353 }),
 					},
 				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: vec![
-					String::from("{% raw %}\n  {% render 'button', link: '@/collections/all' %}\n  sadsad"),
-					String::from("{% render 'button', link: '/collections/all' %}")
+					ExampleBlock {
+						body: String::from(
+							"{% raw %}\n    {% render 'button', link: '@/collections/all' %}\n    sadsad @param asdasd\n{% endraw %}\n\ntest"
+						),
+						span: Span { start: 144, end: 265 }
+					},
+					ExampleBlock {
+						body: String::from("{% render 'button', link: '/collections/all' %}"),
+						span: Span { start: 381, end: 439 }
+					}
 				]
 			})
 		);
@@ -840,32 +2132,47 @@ Intended for use @ description foo in a block similar to the button block.
 				description: String::from(
 					"Intended for use @ description foo in a block similar to the button block.\n  more lines here\n  end"
 				),
+				deprecated: None,
 				param: vec![
 					Param {
 						name: String::from("link"),
 						description: Some(String::from("link to render")),
 						type_: Some(ParamType::String),
 						optional: false,
+						span: Some(Span { start: 103, end: 140 }),
 					},
 					Param {
 						name: String::from("block"),
 						description: Some(String::from("The block @param things and what not")),
 						type_: Some(ParamType::Object),
 						optional: true,
+						span: Some(Span { start: 266, end: 338 }),
 					},
 					Param {
 						name: String::from("foo"),
 						description: None,
 						type_: None,
 						optional: true,
+						span: Some(Span { start: 341, end: 353 }),
 					},
 				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: vec![
-					String::from(
-						"{% raw %}\n  {% render 'button', link: '@/collections/all' %}\n  sadsad @ param asdasd\n{% endraw %}\n\ntest"
-					),
-					String::from("{% render 'button', link: '/collections/all' %}"),
-					String::from("test")
+					ExampleBlock {
+						body: String::from(
+							"{% raw %}\n    {% render 'button', link: '@/collections/all' %}\n    sadsad @ param asdasd\n{% endraw %}\n\ntest"
+						),
+						span: Span { start: 143, end: 265 }
+					},
+					ExampleBlock {
+						body: String::from("{% render 'button', link: '/collections/all' %}"),
+						span: Span { start: 381, end: 439 }
+					},
+					ExampleBlock {
+						body: String::from("test"),
+						span: Span { start: 443, end: 458 }
+					}
 				]
 			})
 		);
@@ -874,29 +2181,88 @@ Intended for use @ description foo in a block similar to the button block.
 			LiquidDocs::parse_doc_content("Description with words\n @param {collection} foo - bar\n\n end\n"),
 			Ok(DocBlock {
 				description: String::from("Description with words"),
+				deprecated: None,
 				param: vec![Param {
 					name: String::from("foo"),
 					description: Some(String::from("bar")),
 					type_: Some(ParamType::Shopify(String::from("collection"))),
 					optional: false,
+					span: Some(Span { start: 24, end: 53 }),
 				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new(),
 			})
 		);
+
+		// `# @param` comments inside a `{% liquid %}` tag of an example are part of the example code
+		assert_eq!(
+			LiquidDocs::parse_doc_content(
+				"Renders a card\n@param {string} title\n@example\n{% liquid\n  # @param {string} ignored\n  render 'card', title: title\n%}\n@param {number} count"
+			),
+			Ok(DocBlock {
+				description: String::from("Renders a card"),
+				deprecated: None,
+				param: vec![
+					Param {
+						name: String::from("title"),
+						description: None,
+						type_: Some(ParamType::String),
+						optional: false,
+						span: Some(Span { start: 15, end: 36 }),
+					},
+					Param {
+						name: String::from("count"),
+						description: None,
+						type_: Some(ParamType::Number),
+						optional: false,
+						span: Some(Span { start: 117, end: 138 }),
+					},
+				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% liquid\n  # @param {string} ignored\n  render 'card', title: title\n%}"),
+					span: Span { start: 37, end: 116 }
+				}],
+			})
+		);
 	}
 
 	#[test]
 	fn parse_doc_content_param_param_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param [  foo  ] - desc"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("desc")),
+					type_: None,
+					optional: true,
+					span: Some(Span { start: 0, end: 23 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new(),
+			})
+		);
+
 		assert_eq!(
 			LiquidDocs::parse_doc_content("@param foo "),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
 				param: vec![Param {
 					name: String::from("foo"),
 					description: None,
 					type_: None,
 					optional: false,
+					span: Some(Span { start: 0, end: 10 }),
 				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
@@ -905,98 +2271,661 @@ Intended for use @ description foo in a block similar to the button block.
 			LiquidDocs::parse_doc_content("Description with words\n@param foo bar"),
 			Ok(DocBlock {
 				description: String::from("Description with words"),
+				deprecated: None,
 				param: vec![Param {
 					name: String::from("foo"),
 					description: Some(String::from("bar")),
 					type_: None,
 					optional: false,
+					span: Some(Span { start: 23, end: 37 }),
 				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
 				example: Vec::new()
 			})
 		);
 
 		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param {string} foo bar"),
+			LiquidDocs::parse_doc_content("Description with words\n@param {string} foo bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::String),
+					optional: false,
+					span: Some(Span { start: 23, end: 46 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n@param {string} [foo] bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::String),
+					optional: true,
+					span: Some(Span { start: 23, end: 48 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n@param {string[]  } [foo] bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::ArrayOf(Box::new(ParamType::String))),
+					optional: true,
+					span: Some(Span { start: 23, end: 52 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n@param {  number[]} [foo] bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Number))),
+					optional: true,
+					span: Some(Span { start: 23, end: 52 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n@param { boolean[] } foo bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Boolean))),
+					optional: false,
+					span: Some(Span { start: 23, end: 51 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n@param {object[]} foo bar"),
+			Ok(DocBlock {
+				description: String::from("Description with words"),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: Some(String::from("bar")),
+					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Object))),
+					optional: false,
+					span: Some(Span { start: 23, end: 48 }),
+				},],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new()
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_param_group_test() {
+		let doc_block = LiquidDocs::parse_doc_content(
+			"@param title\n@param-group \"Layout\"\n@param {number} columns\n@param [gap]\n@param-group Colors\n@param color\n@param-group \"Empty\"\n@example\nfoo",
+		)
+		.unwrap();
+
+		assert_eq!(
+			doc_block.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(),
+			vec!["title", "columns", "gap", "color"]
+		);
+		assert_eq!(
+			doc_block
+				.param_groups
+				.iter()
+				.map(|group| (group.name.as_str(), group.params.iter().map(|param| param.name.as_str()).collect::<Vec<_>>()))
+				.collect::<Vec<_>>(),
+			vec![
+				("Layout", vec!["columns", "gap"]),
+				("Colors", vec!["color"]),
+				("Empty", Vec::new())
+			]
+		);
+		assert_eq!(doc_block.param_groups[0].params[0], doc_block.param[1]);
+		assert_eq!(doc_block.example.len(), 1);
+	}
+
+	#[test]
+	fn parse_doc_content_param_enum_test() {
+		let doc_block =
+			LiquidDocs::parse_doc_content("@param {enum: \"left\"|'right'} align - Alignment\n@param {enum:'a'|'b'[]} list")
+				.unwrap();
+		assert_eq!(doc_block.param[0].type_, Some(ParamType::Enum(vec![String::from("left"), String::from("right")])));
+		assert_eq!(
+			doc_block.param[1].type_,
+			Some(ParamType::ArrayOf(Box::new(ParamType::Enum(vec![String::from("a"), String::from("b")]))))
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {enum: left|right} align"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("enum: left|right"),
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_param_description_dash_test() {
+		for (content, description) in [
+			("@param {string} foo - The description", "The description"),
+			("@param {string} foo The description", "The description"),
+			("@param {string} foo -The description", "The description"),
+			("@param foo The - description", "The - description"),
+			("@param [foo] - - The description", "- The description"),
+			// only a hyphen separates the name from the description, other dashes are part of the text
+			("@param {string} foo – The description", "– The description"),
+		] {
+			assert_eq!(
+				LiquidDocs::parse_doc_content(content).unwrap().param[0].description.as_deref(),
+				Some(description),
+				"{content}"
+			);
+		}
+	}
+
+	#[test]
+	fn parse_doc_content_param_description_braces_test() {
+		// the type braces are consumed before the description so braces in the description are plain text
+		let doc_block = LiquidDocs::parse_doc_content("@param {string} foo - uses {curly braces}").unwrap();
+		assert_eq!(doc_block.param[0].type_, Some(ParamType::String));
+		assert_eq!(doc_block.param[0].description.as_deref(), Some("uses {curly braces}"));
+
+		let doc_block = LiquidDocs::parse_doc_content("@param foo - uses {curly braces}").unwrap();
+		assert_eq!(doc_block.param[0].type_, None);
+		assert_eq!(doc_block.param[0].description.as_deref(), Some("uses {curly braces}"));
+	}
+
+	#[test]
+	fn parse_doc_content_param_empty_type_test() {
+		for content in [
+			"@param {  } foo - description",
+			"@param {} foo - description",
+			"@param {\t} foo - description",
+		] {
+			let doc_block = LiquidDocs::parse_doc_content(content).unwrap();
+			assert_eq!(doc_block.param[0].name, "foo", "{content}");
+			assert_eq!(doc_block.param[0].type_, None, "{content}");
+			assert_eq!(doc_block.param[0].description.as_deref(), Some("description"), "{content}");
+		}
+
+		let doc_block = LiquidDocs::parse_doc_content("@param { } [foo]").unwrap();
+		assert_eq!(doc_block.param[0].type_, None);
+		assert!(doc_block.param[0].optional);
+
+		// an array of nothing is still an unknown type
+		assert!(matches!(
+			LiquidDocs::parse_doc_content("@param { [] } foo"),
+			Err(ParsingError::UnknownParameterType { ref offending_type, .. }) if offending_type.is_empty()
+		));
+		assert!(matches!(LiquidDocs::parse_doc_content("@param {"), Err(ParsingError::UnexpectedParameterEnd { .. })));
+	}
+
+	#[test]
+	fn parse_doc_content_param_blank_description_test() {
+		let doc_block = LiquidDocs::parse_doc_content("@param {string} foo -   \n@param bar -\t\n@param baz   ").unwrap();
+		assert_eq!(doc_block.param.len(), 3);
+		assert!(doc_block.param.iter().all(|param| param.description.is_none()));
+	}
+
+	#[test]
+	fn shopify_objects_exclude_primitive_types_test() {
+		// the generated list is checked here so CI fails as soon as the updater adds a name that would never be reached
+		for name in PRIMITIVE_TYPES {
+			assert!(!SHOPIFY_ALLOWED_OBJECTS.contains(&name), "{name}");
+			assert!(!SETTINGS_OBJECTS.contains(&name), "{name}");
+		}
+	}
+
+	#[test]
+	fn parse_doc_content_param_shopify_objects_test() {
+		// Objects that can only be reached through other objects are valid types, see https://shopify.dev/docs/api/liquid/objects
+		for object in [
+			"collection",
+			"linklist",
+			"link",
+			"product",
+			"variant",
+			"image",
+			"article",
+			"blog",
+			"metafield",
+			"forloop",
+			"tablerowloop",
+			"section_settings",
+			"block_settings",
+		] {
+			assert_eq!(
+				LiquidDocs::parse_doc_content(&format!("@param {{{object}}} foo")).unwrap().param[0].type_,
+				Some(ParamType::Shopify(String::from(object))),
+				"{object} should be a valid param type"
+			);
+		}
+
+		// Global objects are available everywhere so Shopify doesn't accept them as param types
+		for object in ["collections", "linklists", "shop", "cart", "all_products"] {
+			assert!(
+				matches!(
+					LiquidDocs::parse_doc_content(&format!("@param {{{object}}} foo")),
+					Err(ParsingError::UnknownParameterType { .. })
+				),
+				"{object} should not be a valid param type"
+			);
+		}
+	}
+
+	#[test]
+	fn parse_doc_content_link_test() {
+		let doc_block = LiquidDocs::parse_doc_content(
+			"Card\n@link https://example.com/card \"Card docs\"\n@link  https://example.com\n@link\n@param foo",
+		)
+		.unwrap();
+		assert_eq!(doc_block.description, "Card");
+		assert_eq!(
+			doc_block.links,
+			vec![
+				DocLink {
+					url: String::from("https://example.com/card"),
+					label: Some(String::from("Card docs")),
+				},
+				DocLink {
+					url: String::from("https://example.com"),
+					label: None,
+				},
+			]
+		);
+		assert_eq!(doc_block.param[0].name, "foo");
+
+		assert_eq!(
+			LiquidDocs::parse_link("https://example.com Changelog"),
+			Some(DocLink {
+				url: String::from("https://example.com"),
+				label: Some(String::from("Changelog")),
+			})
+		);
+		assert_eq!(LiquidDocs::parse_link(""), None);
+	}
+
+	#[test]
+	fn parse_doc_content_param_at_end_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param foo"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: None,
+					type_: None,
+					optional: false,
+					span: Some(Span { start: 0, end: 10 }),
+				}],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new(),
+			})
+		);
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {string} foo"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: vec![Param {
+					name: String::from("foo"),
+					description: None,
+					type_: Some(ParamType::String),
+					optional: false,
+					span: Some(Span { start: 0, end: 19 }),
+				}],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new(),
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_unicode_type_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {Ürün} foo - bar"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("Ürün"),
+			})
+		);
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param { 商品[] } foo"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("商品"),
+			})
+		);
+		assert!(matches!(LiquidDocs::parse_doc_content("@param {Ürün"), Err(ParsingError::UnexpectedParameterEnd { .. })));
+	}
+
+	#[test]
+	fn parse_doc_content_unclosed_array_type_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {string[} foo"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("string["),
+			})
+		);
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {string[][} foo"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("string[]["),
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_named_object_test() {
+		let config = ParserConfig {
+			named_object_schemas: vec![String::from("article-card")],
+			..Default::default()
+		};
+		let doc_block = LiquidDocs::parse_doc_content_with_config(
+			"@param {object: \"article-card\"} card\n@param {object: 'article-card'[]} cards",
+			0,
+			&config,
+		)
+		.unwrap();
+		assert_eq!(doc_block.param[0].type_, Some(ParamType::NamedObject(String::from("article-card"))));
+		assert_eq!(
+			doc_block.param[1].type_,
+			Some(ParamType::ArrayOf(Box::new(ParamType::NamedObject(String::from("article-card")))))
+		);
+
+		// unregistered schemas are unknown just like misspelled Shopify objects
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {object: \"article-card\"} card"),
+			Err(ParsingError::UnknownParameterType {
+				line: 1,
+				column: 8,
+				offending_type: String::from("object: \"article-card\""),
+			})
+		);
+		assert!(matches!(
+			LiquidDocs::parse_doc_content_with_config("@param {object: article-card} card", 0, &config),
+			Err(ParsingError::UnknownParameterType { .. })
+		));
+	}
+
+	#[test]
+	fn parse_doc_content_constrained_type_test() {
+		let doc_block =
+			LiquidDocs::parse_doc_content("@param {string: min=1, max=100} title\n@param {number:max=10[]} ratings").unwrap();
+		assert_eq!(
+			doc_block.param[0].type_,
+			Some(ParamType::Constrained {
+				base: ConstrainedBase::String,
+				constraints: BTreeMap::from([
+					(String::from("min"), String::from("1")),
+					(String::from("max"), String::from("100")),
+				]),
+			})
+		);
+		assert_eq!(
+			doc_block.param[1].type_,
+			Some(ParamType::ArrayOf(Box::new(ParamType::Constrained {
+				base: ConstrainedBase::Number,
+				constraints: BTreeMap::from([(String::from("max"), String::from("10"))]),
+			})))
+		);
+
+		for type_name in [
+			"boolean: min=1",
+			"string: min",
+			"string: =1",
+			"string:",
+			"string: min=1,",
+		] {
+			assert!(
+				matches!(
+					LiquidDocs::parse_doc_content(&format!("@param {{{type_name}}} foo")),
+					Err(ParsingError::UnknownParameterType { .. })
+				),
+				"{type_name} should not be a valid param type"
+			);
+		}
+	}
+
+	#[test]
+	fn parse_doc_content_any_type_test() {
+		let doc_block = LiquidDocs::parse_doc_content("@param {any} value\n@param {any[]} values").unwrap();
+		assert_eq!(doc_block.param[0].type_, Some(ParamType::Any));
+		assert_eq!(doc_block.param[1].type_, Some(ParamType::ArrayOf(Box::new(ParamType::Any))));
+		assert_eq!(doc_block.param[1].base_type(), Some(&ParamType::Any));
+
+		assert!(matches!(
+			LiquidDocs::parse_doc_content("@param {Any} value"),
+			Err(ParsingError::UnknownParameterType { .. })
+		));
+	}
+
+	#[test]
+	fn parse_doc_content_array_depth_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param {number[][]} grid").unwrap().param[0].type_,
+			Some(ParamType::ArrayOf(Box::new(ParamType::ArrayOf(Box::new(ParamType::Number)))))
+		);
+
+		let deep_type = format!("@param {{string{}}} foo", "[]".repeat(100));
+		assert_eq!(
+			LiquidDocs::parse_doc_content(&deep_type),
+			Err(ParsingError::ArrayNestingTooDeep {
+				line: 1,
+				column: 8,
+				depth: 100,
+				max: 5
+			})
+		);
+
+		let config = ParserConfig {
+			max_array_depth: 1,
+			..Default::default()
+		};
+		assert!(LiquidDocs::parse_doc_content_with_config("@param {string[]} foo", 0, &config).is_ok());
+		assert_eq!(
+			LiquidDocs::parse_doc_content_with_config("Card\n  @param {string[][]} foo", 0, &config),
+			Err(ParsingError::ArrayNestingTooDeep {
+				line: 2,
+				column: 10,
+				depth: 2,
+				max: 1
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_param_continuation_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content(
+				"Description\n@param {string} foo - First line\n  continuation line\n    deeper line\nnot part of it\n@param bar"
+			),
+			Ok(DocBlock {
+				description: String::from("Description"),
+				deprecated: None,
+				param: vec![
+					Param {
+						name: String::from("foo"),
+						description: Some(String::from("First line\ncontinuation line\ndeeper line")),
+						type_: Some(ParamType::String),
+						optional: false,
+						span: Some(Span { start: 12, end: 80 }),
+					},
+					Param {
+						name: String::from("bar"),
+						description: None,
+						type_: None,
+						optional: false,
+						span: Some(Span { start: 96, end: 106 }),
+					},
+				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new(),
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content(
+				"  Description\n  @param foo\n    The description\n  @param bar - bar\n  same level\n\n    after blank"
+			),
+			Ok(DocBlock {
+				description: String::from("Description"),
+				deprecated: None,
+				param: vec![
+					Param {
+						name: String::from("foo"),
+						description: Some(String::from("The description")),
+						type_: None,
+						optional: false,
+						span: Some(Span { start: 16, end: 46 }),
+					},
+					Param {
+						name: String::from("bar"),
+						description: Some(String::from("bar")),
+						type_: None,
+						optional: false,
+						span: Some(Span { start: 49, end: 65 }),
+					},
+				],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: Vec::new(),
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@param foo - First line\n  @example\n  {% render 'foo' %}"),
 			Ok(DocBlock {
-				description: String::from("Description with words"),
+				description: String::new(),
+				deprecated: None,
 				param: vec![Param {
 					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::String),
+					description: Some(String::from("First line")),
+					type_: None,
 					optional: false,
-				},],
-				example: Vec::new()
+					span: Some(Span { start: 0, end: 23 }),
+				}],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% render 'foo' %}"),
+					span: Span { start: 26, end: 55 }
+				}],
 			})
 		);
+	}
 
+	#[test]
+	fn parse_doc_content_example_backslash_test() {
+		let content = r#"@example
+  {% render 'snippets\card' %}
+  {{ 'C:\new\test' | append: "\r\n\t\\" }}"#;
 		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param {string} [foo] bar"),
-			Ok(DocBlock {
-				description: String::from("Description with words"),
-				param: vec![Param {
-					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::String),
-					optional: true,
-				},],
-				example: Vec::new()
-			})
+			LiquidDocs::parse_doc_content(content).unwrap().example[0].body,
+			r#"{% render 'snippets\card' %}
+{{ 'C:\new\test' | append: "\r\n\t\\" }}"#
 		);
+	}
 
-		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param {string[]  } [foo] bar"),
-			Ok(DocBlock {
-				description: String::from("Description with words"),
-				param: vec![Param {
-					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::ArrayOf(Box::new(ParamType::String))),
-					optional: true,
-				},],
-				example: Vec::new()
-			})
-		);
+	#[test]
+	fn parse_doc_content_column_mode_test() {
+		let content = "Über 🎉 @param {unknown} foo";
+		let error = ParsingError::UnknownParameterType {
+			line: 1,
+			column: 19,
+			offending_type: String::from("unknown"),
+		};
+		assert_eq!(LiquidDocs::parse_doc_content(content), Err(error));
+
+		let config = ParserConfig {
+			column_mode: ColumnMode::Characters,
+			..Default::default()
+		};
+		let error = ParsingError::UnknownParameterType {
+			line: 1,
+			column: 15,
+			offending_type: String::from("unknown"),
+		};
+		assert_eq!(LiquidDocs::parse_doc_content_with_config(content, 0, &config), Err(error));
 
+		// only the line of the error counts
+		let content = "名前 🎉\n@param {unknown} foo";
+		assert_eq!(LiquidDocs::parse_doc_content_with_config(content, 0, &config).unwrap_err().get_column(), 8);
+
+		let result = LiquidDocs::parse_with_config(&format!("{{% doc %}}{content}{{% enddoc %}}"), &config);
+		assert_eq!(result.errors[0].column, 8);
+	}
+
+	#[test]
+	fn parse_doc_content_example_tab_width_test() {
+		// the tab and the four spaces are the same visual indentation
+		let content = "@example\n\t{% if true %}\n\t\t{{ foo }}\n    {% endif %}";
 		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param {  number[]} [foo] bar"),
-			Ok(DocBlock {
-				description: String::from("Description with words"),
-				param: vec![Param {
-					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Number))),
-					optional: true,
-				},],
-				example: Vec::new()
-			})
+			LiquidDocs::parse_doc_content(content).unwrap().example[0].body,
+			"{% if true %}\n\t{{ foo }}\n{% endif %}"
 		);
 
+		let config = ParserConfig {
+			tab_width: 2,
+			..Default::default()
+		};
 		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param { boolean[] } foo bar"),
-			Ok(DocBlock {
-				description: String::from("Description with words"),
-				param: vec![Param {
-					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Boolean))),
-					optional: false,
-				},],
-				example: Vec::new()
-			})
+			LiquidDocs::parse_doc_content_with_config(content, 0, &config).unwrap().example[0].body,
+			"{% if true %}\n\t{{ foo }}\n  {% endif %}"
 		);
 
+		// a tab reaching past the common indentation keeps its remaining columns as spaces
 		assert_eq!(
-			LiquidDocs::parse_doc_content("Description with words\n@param {object[]} foo bar"),
-			Ok(DocBlock {
-				description: String::from("Description with words"),
-				param: vec![Param {
-					name: String::from("foo"),
-					description: Some(String::from("bar")),
-					type_: Some(ParamType::ArrayOf(Box::new(ParamType::Object))),
-					optional: false,
-				},],
-				example: Vec::new()
-			})
+			LiquidDocs::parse_doc_content("@example\n  {% if true %}\n\t{{ foo }}\n  {% endif %}").unwrap().example[0].body,
+			"{% if true %}\n  {{ foo }}\n{% endif %}"
 		);
 	}
 
@@ -1013,8 +2942,14 @@ Intended for use @ description foo in a block similar to the button block.
 			),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
 				param: Vec::new(),
-				example: vec![String::from("{% raw %}\n\t{% render 'card' %}\n{% endraw %}")],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% raw %}\n\t{% render 'card' %}\n{% endraw %}"),
+					span: Span { start: 1, end: 53 }
+				}],
 			})
 		);
 
@@ -1029,8 +2964,14 @@ Intended for use @ description foo in a block similar to the button block.
 			),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
 				param: Vec::new(),
-				example: vec![String::from("{% raw %}\n\t{% render 'card' %}\n{% endraw %}")],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% raw %}\n\t\t\t\t\t{% render 'card' %}\n{% endraw %}"),
+					span: Span { start: 5, end: 69 }
+				}],
 			})
 		);
 
@@ -1045,8 +2986,14 @@ Intended for use @ description foo in a block similar to the button block.
 			),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
 				param: Vec::new(),
-				example: vec![String::from("{% raw %}\n\t{% render 'card' %}\n{% endraw %}")],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% raw %}\n\t\t\t\t\t\t{% render 'card' %}\n{% endraw %}"),
+					span: Span { start: 5, end: 72 }
+				}],
 			})
 		);
 
@@ -1061,8 +3008,14 @@ Intended for use @ description foo in a block similar to the button block.
 			),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
 				param: Vec::new(),
-				example: vec![String::from("{% raw %}\n{% render 'card' %}\n{% endraw %}")],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("\t\t\t\t{% raw %}\n\t\t\t{% render 'card' %}\n{% endraw %}"),
+					span: Span { start: 5, end: 65 }
+				}],
 			})
 		);
 
@@ -1070,10 +3023,229 @@ Intended for use @ description foo in a block similar to the button block.
 			LiquidDocs::parse_doc_content("@example\n{% raw %}\n{% render 'card' %}\n{% endraw %}"),
 			Ok(DocBlock {
 				description: String::new(),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% raw %}\n{% render 'card' %}\n{% endraw %}"),
+					span: Span { start: 0, end: 51 }
+				}],
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_example_min_indentation_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@example\n    {% render 'card',\n  title: 'Foo'\n  %}"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("  {% render 'card',\ntitle: 'Foo'\n%}"),
+					span: Span { start: 0, end: 50 }
+				}],
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@example\n\n\n    {% render 'card',\n\n      title: 'Foo'\n    %}\n\n"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% render 'card',\n\n  title: 'Foo'\n%}"),
+					span: Span { start: 0, end: 59 }
+				}],
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@example {% render 'card',\n  title: 'Foo'\n%}"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% render 'card',\n  title: 'Foo'\n%}"),
+					span: Span { start: 0, end: 44 }
+				}],
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_example_raw_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content(
+				"@example\n  {% render 'a' %}\n  {%- raw -%}\n      {{ foo }}\n   {% endraw %}\n  {% render 'b' %}"
+			),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
+				param: Vec::new(),
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% render 'a' %}\n{%- raw -%}\n      {{ foo }}\n {% endraw %}\n{% render 'b' %}"),
+					span: Span { start: 0, end: 92 }
+				}],
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("@example\n  {% raw %}{{ foo }}{% endraw %}\n    {% render 'b' %}"),
+			Ok(DocBlock {
+				description: String::new(),
+				deprecated: None,
 				param: Vec::new(),
-				example: vec![String::from("{% raw %}\n{% render 'card' %}\n{% endraw %}")],
+				param_groups: Vec::new(),
+				links: Vec::new(),
+				example: vec![ExampleBlock {
+					body: String::from("{% raw %}{{ foo }}{% endraw %}\n  {% render 'b' %}"),
+					span: Span { start: 0, end: 62 }
+				}],
+			})
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn parse_doc_content_from_reader_test() {
+		assert_eq!(
+			LiquidDocs::parse_doc_content_from_reader("Description\n@param foo".as_bytes()),
+			LiquidDocs::parse_doc_content("Description\n@param foo")
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content_from_reader(&[0xff, 0xfe][..]),
+			Err(ParsingError::ReadError {
+				message: String::from("stream did not contain valid UTF-8")
+			})
+		);
+	}
+
+	#[test]
+	fn parse_doc_content_at_offset_test() {
+		let file = "<p></p>\n{% doc %}\n  Renders a card\n  @param {unknown} title\n{% enddoc %}\n{% doc %}@param {foo} bar{% enddoc %}";
+		let spans =
+			LiquidDocs::extract_doc_blocks_with_spans(file).unwrap().into_iter().map(|(span, _)| span).collect::<Vec<_>>();
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content_at_offset(file, spans[0].start),
+			Err(ParsingError::UnknownParameterType {
+				line: 4,
+				column: 10,
+				offending_type: String::from("unknown"),
+			})
+		);
+		assert_eq!(
+			LiquidDocs::parse_doc_content_at_offset(file, spans[1].start),
+			Err(ParsingError::UnknownParameterType {
+				line: 6,
+				column: 17,
+				offending_type: String::from("foo"),
 			})
 		);
+
+		let file = "<p></p>\n{% doc %}\n  @param {string} title\n{% enddoc %}";
+		let doc_block = LiquidDocs::parse_doc_content_at_offset(file, 17).unwrap();
+		let span = doc_block.param[0].span.unwrap();
+		assert_eq!(&file[span.start..span.end], "@param {string} title");
+
+		assert_eq!(LiquidDocs::parse_doc_content_at_offset(file, 100), Err(ParsingError::NoDocContentFound));
+	}
+
+	#[test]
+	fn parse_param_ref_test() {
+		let content = "  @param {string} [title] - The title\n@param count";
+		let param = LiquidDocs::parse_param_ref(content).unwrap();
+		assert!(matches!(param.name, Cow::Borrowed("title")));
+		assert!(matches!(param.description, Some(Cow::Borrowed("The title"))));
+		assert_eq!(param.span, Some(Span { start: 2, end: 37 }));
+		assert_eq!(param.into_owned(), LiquidDocs::parse_doc_content(content).unwrap().param[0]);
+
+		let param = LiquidDocs::parse_param_ref("@param title - The\n   long title").unwrap();
+		assert!(matches!(param.description, Some(Cow::Owned(ref description)) if description == "The\nlong title"));
+
+		assert_eq!(LiquidDocs::parse_param_ref("Description"), Err(ParsingError::NoDocContentFound));
+		assert_eq!(LiquidDocs::parse_param_ref("@param-group \"Layout\""), Err(ParsingError::NoDocContentFound));
+		assert!(matches!(
+			LiquidDocs::parse_param_ref("@param {unknown} foo"),
+			Err(ParsingError::UnknownParameterType { .. })
+		));
+	}
+
+	#[test]
+	fn parse_doc_content_lenient_test() {
+		let (doc_block, errors, warnings) = LiquidDocs::parse_doc_content_lenient(
+			"Description\n@param {string} title\n@param {unknown} broken\n@param [alt\n@param {number} count\n@example\nfoo",
+		);
+		let doc_block = doc_block.unwrap();
+
+		assert_eq!(doc_block.description, "Description");
+		assert_eq!(doc_block.param.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["title", "count"]);
+		assert_eq!(doc_block.example_bodies().collect::<Vec<_>>(), vec!["foo"]);
+		assert_eq!(
+			errors,
+			vec![
+				ParsingError::UnknownParameterType {
+					line: 3,
+					column: 8,
+					offending_type: String::from("unknown"),
+				},
+				ParsingError::MissingOptionalClosingBracket {
+					line: 4,
+					column: 9,
+					offending_line: String::from("@param [alt"),
+				},
+			]
+		);
+		assert!(warnings.is_empty());
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content_lenient("Description"),
+			(LiquidDocs::parse_doc_content("Description").ok(), vec![], vec![])
+		);
+		assert_eq!(LiquidDocs::parse_doc_content_lenient(""), (None, vec![ParsingError::NoDocContentFound], vec![]));
+		assert_eq!(
+			LiquidDocs::parse_doc_content_lenient("@param {unknown} foo"),
+			(
+				None,
+				vec![ParsingError::UnknownParameterType {
+					line: 1,
+					column: 8,
+					offending_type: String::from("unknown"),
+				}],
+				vec![]
+			)
+		);
+
+		// warnings are reported next to the errors instead of being dropped
+		let (doc_block, errors, warnings) =
+			LiquidDocs::parse_doc_content_lenient("@param cart\n@param {unknown} broken\n@example\n@param 0");
+		assert_eq!(doc_block.unwrap().param_count(), 2);
+		assert_eq!(errors.len(), 1);
+		assert_eq!(
+			warnings,
+			vec![
+				ParseWarning::ParamShadowsBuiltin {
+					name: String::from("cart"),
+					line: 1,
+				},
+				ParseWarning::EmptyExample { line: 3 },
+				ParseWarning::MixedPositionalParams { line: 4 },
+			]
+		);
 	}
 
 	#[test]
@@ -1160,6 +3332,66 @@ Intended for use @ description foo in a block similar to the button block.
 				offending_type: String::from("unknown"),
 			})
 		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n @param foo{bar} - baz"),
+			Err(ParsingError::InvalidParameterName {
+				line: 2,
+				column: 9,
+				offending_line: String::from("@param foo{bar} - baz"),
+			})
+		);
+
+		assert_eq!(
+			LiquidDocs::parse_doc_content("Description with words\n @param [foo{bar}] - baz"),
+			Err(ParsingError::InvalidParameterName {
+				line: 2,
+				column: 10,
+				offending_line: String::from("@param [foo{bar}] - baz"),
+			})
+		);
+	}
+
+	#[test]
+	fn parsing_error_is_error_test() {
+		let error: Box<dyn core::error::Error> = Box::new(ParsingError::NoDocContentFound);
+		assert_eq!(error.to_string(), "No doc content found");
+	}
+
+	#[test]
+	fn with_context_test() {
+		let content = "Description with words\n\t@param {unknown} foo - bar\n\nend\n";
+		let error = LiquidDocs::parse_doc_content(content).unwrap_err();
+
+		assert_eq!(
+			error.with_context(content, 1, ColumnMode::Bytes).to_string(),
+			"Unknown parameter type on 2:9: \"unknown\"\n1 | Description with words\n2 | \t@param {unknown} foo - bar\n  | \t       ^\n3 | "
+		);
+
+		let error = LiquidDocs::parse_doc_content(content).unwrap_err();
+		assert_eq!(
+			error.with_context(content, 0, ColumnMode::Bytes).snippet,
+			"2 | \t@param {unknown} foo - bar\n  | \t       ^"
+		);
+
+		assert_eq!(
+			ParsingError::NoDocContentFound.with_context(content, 2, ColumnMode::Bytes).to_string(),
+			"No doc content found"
+		);
+
+		// the pointer lands on the same character no matter how the column was counted
+		let content = "Über 🎉 @param {unknown} foo";
+		let expected = "1 | Über 🎉 @param {unknown} foo\n  |               ^";
+		let error = LiquidDocs::parse_doc_content(content).unwrap_err();
+		assert_eq!(error.with_context(content, 0, ColumnMode::Bytes).snippet, expected);
+
+		let config = ParserConfig {
+			column_mode: ColumnMode::Characters,
+			..Default::default()
+		};
+		let error = LiquidDocs::parse_doc_content_with_config(content, 0, &config).unwrap_err();
+		assert_eq!(error.get_column(), 15);
+		assert_eq!(error.with_context(content, 0, ColumnMode::Characters).snippet, expected);
 	}
 
 	#[test]
@@ -1255,6 +3487,7 @@ Intended for use @ description foo in a block similar to the button block.
 	}
 
 	#[test]
+	#[allow(clippy::bool_assert_comparison)]
 	fn peek_matches_test() {
 		let content = "{% liquid";
 		let mut instance = LiquidDocs {
@@ -1275,6 +3508,19 @@ Intended for use @ description foo in a block similar to the button block.
 		assert_eq!(instance.peek_matches("iqui"), false);
 	}
 
+	#[test]
+	fn peek_matches_multi_byte_test() {
+		let content = "@pé";
+		let mut instance = LiquidDocs {
+			content,
+			chars: content.char_indices().peekable(),
+		};
+
+		assert_eq!(instance.chars.next(), Some((0, '@')));
+		assert!(!instance.peek_matches("pa"));
+		assert!(instance.peek_matches("pé"));
+	}
+
 	#[test]
 	fn consume_chars_test() {
 		let content = "0123456789end";
@@ -1411,6 +3657,142 @@ end!
 		assert_eq!(instance.skip_to_tag("endtag", true), Some(39));
 	}
 
+	#[test]
+	fn parse_deprecated_test() {
+		assert_eq!(
+			LiquidDocs::parse_deprecated("2.0.0 Use 'new-snippet' instead. See https://example.com/changelog#2.0.0"),
+			DeprecatedInfo {
+				message: String::from("Use 'new-snippet' instead."),
+				version: Some(String::from("2.0.0")),
+				replacement: Some(String::from("new-snippet")),
+				link: Some(String::from("https://example.com/changelog#2.0.0")),
+			}
+		);
+		assert_eq!(
+			LiquidDocs::parse_deprecated("v3 No longer maintained"),
+			DeprecatedInfo {
+				message: String::from("No longer maintained"),
+				version: Some(String::from("v3")),
+				replacement: None,
+				link: None,
+			}
+		);
+		assert_eq!(
+			LiquidDocs::parse_deprecated("2nd try. Use \"card\""),
+			DeprecatedInfo {
+				message: String::from("2nd try. Use \"card\""),
+				version: None,
+				replacement: Some(String::from("card")),
+				link: None,
+			}
+		);
+		assert_eq!(LiquidDocs::parse_deprecated(""), DeprecatedInfo::default());
+	}
+
+	#[test]
+	fn parse_doc_content_deprecated_test() {
+		let doc_block = LiquidDocs::parse_doc_content(
+			"Old card\n@deprecated 1.2.0 Use 'card' instead.\n  See https://example.com.\n@param title\n@deprecated ignored",
+		)
+		.unwrap();
+
+		assert_eq!(doc_block.description, "Old card");
+		assert_eq!(
+			doc_block.deprecated,
+			Some(DeprecatedInfo {
+				message: String::from("Use 'card' instead."),
+				version: Some(String::from("1.2.0")),
+				replacement: Some(String::from("card")),
+				link: Some(String::from("https://example.com")),
+			})
+		);
+		assert_eq!(doc_block.param.len(), 1);
+	}
+
+	#[test]
+	fn parse_enum_values_test() {
+		assert_eq!(
+			LiquidDocs::parse_enum_values(" \"left\" | 'right'|\"center\""),
+			Some(vec![String::from("left"), String::from("right"), String::from("center")])
+		);
+		assert_eq!(LiquidDocs::parse_enum_values("\"left\""), Some(vec![String::from("left")]));
+		assert_eq!(LiquidDocs::parse_enum_values("\"left\"|right"), None);
+		assert_eq!(LiquidDocs::parse_enum_values("\"left'"), None);
+		assert_eq!(LiquidDocs::parse_enum_values("\""), None);
+		assert_eq!(LiquidDocs::parse_enum_values(""), None);
+	}
+
+	#[test]
+	fn check_example_syntax_test() {
+		assert_eq!(LiquidDocs::check_example_syntax("{% render 'card', title: product.title %}"), None);
+		assert_eq!(LiquidDocs::check_example_syntax("{%- if a -%}\n  {{ a | upcase }}\n{%- endif -%}"), None);
+		assert_eq!(LiquidDocs::check_example_syntax("Just text"), None);
+		assert_eq!(
+			LiquidDocs::check_example_syntax("{% render 'foo'"),
+			Some((1, String::from("`{%` is never closed with `%}`")))
+		);
+		assert_eq!(
+			LiquidDocs::check_example_syntax("{{ a }}\n{{ b\n{% render 'foo' %}"),
+			Some((2, String::from("`{{` is never closed with `}}`")))
+		);
+		assert_eq!(
+			LiquidDocs::check_example_syntax("{% if a %}\nrender 'foo' %}"),
+			Some((2, String::from("`%}` without an opening tag")))
+		);
+	}
+
+	#[test]
+	fn parse_example_syntax_warning_test() {
+		let content = "{% doc %}\n@example\n{% render 'a' %}\n@example\n{% render 'b'\n{% enddoc %}";
+		assert!(LiquidDocs::parse(content).warnings.is_empty());
+
+		let config = ParserConfig {
+			validate_example_syntax: true,
+			..Default::default()
+		};
+		let result = LiquidDocs::parse_with_config(content, &config);
+		assert_eq!(result.success[0].example.len(), 2);
+		assert_eq!(result.warnings.len(), 1);
+		assert_eq!(result.warnings[0].line, 5);
+		assert_eq!(
+			result.warnings[0].message,
+			ParseWarning::MalformedExampleSyntax {
+				example_index: 1,
+				line: 5,
+				detail: String::from("`{%` is never closed with `%}`"),
+			}
+			.to_string()
+		);
+		assert!(result.warnings[0].message.contains("on line 5"));
+
+		// the line points into the whole file, also for examples that start on the line of their tag or after blank lines
+		let content = "<p>Card</p>\n{% doc %}\n@example {% render 'a' %}\n{{ a\n@example\n\n  {% render 'b' %}\n  {% render 'c'\n{% enddoc %}";
+		let result = LiquidDocs::parse_with_config(content, &config);
+		assert_eq!(result.warnings.iter().map(|warning| warning.line).collect::<Vec<_>>(), vec![4, 8]);
+	}
+
+	#[test]
+	fn find_tag_in_line_test() {
+		assert_eq!(LiquidDocs::find_tag_in_line("{% raw %}", "raw"), Some(0));
+		assert_eq!(LiquidDocs::find_tag_in_line("  {%- raw -%}", "raw"), Some(2));
+		assert_eq!(LiquidDocs::find_tag_in_line("{{ x }} {%raw%}", "raw"), Some(8));
+		assert_eq!(LiquidDocs::find_tag_in_line("{% endraw %}", "raw"), None);
+		assert_eq!(LiquidDocs::find_tag_in_line("{% endraw %}", "endraw"), Some(0));
+		assert_eq!(LiquidDocs::find_tag_in_line("{% rawfoo %}", "raw"), None);
+		assert_eq!(LiquidDocs::find_tag_in_line("raw", "raw"), None);
+		assert_eq!(LiquidDocs::find_tag_in_line("{% r", "raw"), None);
+	}
+
+	#[test]
+	fn is_inside_liquid_tag_test() {
+		assert!(LiquidDocs::is_inside_liquid_tag("{% liquid\n  # "));
+		assert!(LiquidDocs::is_inside_liquid_tag("{% render 'a' %}\n{%- liquid\n  echo 'a'\n  # "));
+		assert!(!LiquidDocs::is_inside_liquid_tag("{% liquid\n  echo 'a'\n%}\n"));
+		assert!(!LiquidDocs::is_inside_liquid_tag("{% render 'liquid' %} "));
+		assert!(!LiquidDocs::is_inside_liquid_tag("{% liquidfoo "));
+		assert!(!LiquidDocs::is_inside_liquid_tag("liquid "));
+	}
+
 	#[test]
 	fn get_line_and_column_test() {
 		let content = "12345\n678910\n1112131415\n1617181920";
@@ -1446,5 +3828,20 @@ end!
 		assert_eq!(&instance.content[6..7], "6");
 		assert_eq!(instance.cut_till_newline(6), String::from("678910"));
 		assert_eq!(instance.cut_till_newline(8), String::from("8910"));
+
+		let content = format!("@param {{string {}\nnext line", "é".repeat(200));
+		let instance = LiquidDocs {
+			content: &content,
+			chars: content.char_indices().peekable(),
+		};
+		let line = instance.cut_till_newline(0);
+		assert!(line.starts_with("@param {string é"));
+		assert!(line.ends_with("é…"));
+		assert!(line.len() <= MAX_OFFENDING_LINE_BYTES + '…'.len_utf8());
+
+		assert!(matches!(
+			LiquidDocs::parse_doc_content(&format!("@param {{string {}", "x".repeat(1_000_000))),
+			Err(ParsingError::UnexpectedParameterEnd { offending_line, .. }) if offending_line.len() < 300
+		));
 	}
 }
@@ -0,0 +1,6 @@
+//! Built in CI to make sure `liquid-docs` can be used without its default features
+#![no_std]
+
+pub fn documented_params(content: &str) -> usize {
+	liquid_docs::LiquidDocs::parse(content).total_param_count()
+}
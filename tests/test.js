@@ -1,5 +1,5 @@
 const { spawnSync } = require("child_process");
-const { parse } = require("../pkg/liquid_docs.js");
+const { parse, parse_batch } = require("../pkg/liquid_docs.js");
 
 const PARSE_TESTS = [
 	{
@@ -52,32 +52,34 @@ const PARSE_TESTS = [
 					{
 						name: "card_class",
 						description: "Optional class on the parent element",
-						type: "String",
+						type: "string",
 						optional: true,
+						span: { start: 416, end: 481 },
 					},
 					{
 						name: "sizes",
 						description: "The sizes attribute for the image",
-						type: "Number",
+						type: "number",
 						optional: false,
+						span: { start: 482, end: 544 },
 					},
 					{
 						name: "foo",
 						description: "An array of booleans",
-						type: {
-							ArrayOf: "Boolean",
-						},
+						type: "boolean[]",
 						optional: false,
+						span: { start: 545, end: 594 },
 					},
 					{
 						name: "bar",
 						description: "A currency value",
-						type: {
-							Shopify: "currency",
-						},
+						type: "currency",
 						optional: true,
+						span: { start: 595, end: 640 },
 					},
 				],
+				param_groups: [],
+				links: [],
 				example: [],
 			},
 		],
@@ -108,10 +110,13 @@ const PARSE_TESTS = [
 					{
 						name: "images",
 						description: "Some images",
-						type: "Object",
+						type: "object",
 						optional: false,
+						span: { start: 32, end: 68 },
 					},
 				],
+				param_groups: [],
+				links: [],
 				example: [],
 			},
 			{
@@ -120,20 +125,51 @@ const PARSE_TESTS = [
 					{
 						name: "url",
 						description: "Link URL",
-						type: "String",
+						type: "string",
 						optional: false,
+						span: { start: 234, end: 266 },
 					},
 					{
 						name: "open",
 						description: "Open in new tab",
-						type: "Boolean",
+						type: "boolean",
 						optional: false,
+						span: { start: 269, end: 308 },
 					},
 				],
+				param_groups: [],
+				links: [],
 				example: [],
 			},
 		],
 	},
+	{
+		title: "Constrained type",
+		content: `
+{% doc %}
+  @param {string: min=1, max=100} title - The title
+{% enddoc %}
+`,
+		expected: [
+			{
+				description: "",
+				param: [
+					{
+						name: "title",
+						description: "The title",
+						type: { type: "string", constraints: { max: "100", min: "1" } },
+						optional: false,
+						span: { start: 13, end: 62 },
+					},
+				],
+				param_groups: [],
+				links: [],
+				example: [],
+			},
+		],
+		// constraints have to arrive as a plain object, a Map would hide them from property access and JSON.stringify
+		check: (result) => result.success[0].param[0].type.constraints.min === "1",
+	},
 ];
 
 console.log("\x1B[4mRUNNING PARSING TESTS\x1B[0m");
@@ -141,7 +177,10 @@ let failed = 0;
 PARSE_TESTS.forEach((test) => {
 	process.stdout.write(`Running test "${test.title}" `);
 	let result = parse(test.content);
-	if (JSON.stringify(result.success) !== JSON.stringify(test.expected)) {
+	if (
+		JSON.stringify(result.success) !== JSON.stringify(test.expected) ||
+		(test.check && !test.check(result))
+	) {
 		process.stdout.write(
 			`\x1B[41m FAILED \x1B[49m\n  Expected: ${JSON.stringify(test.expected)}\n  Got:      ${JSON.stringify(result.success)}\n`,
 		);
@@ -163,6 +202,57 @@ if (failed == 0) {
 	process.exit(1);
 }
 
+const ERROR_TESTS = [
+	{
+		title: "parse_batch without input",
+		run: () => parse_batch(undefined),
+		code: "InvalidInput",
+	},
+	{
+		title: "parse_batch with null",
+		run: () => parse_batch(null),
+		code: "InvalidInput",
+	},
+	{
+		title: "parse with a number",
+		run: () => parse(42),
+		code: "InvalidInput",
+	},
+];
+
+console.log("\n\x1B[4mRUNNING ERROR TESTS\x1B[0m");
+failed = 0;
+ERROR_TESTS.forEach((test) => {
+	process.stdout.write(`Running test "${test.title}" `);
+	let error;
+	try {
+		test.run();
+	} catch (e) {
+		error = e;
+	}
+
+	if (error && error.code === test.code && typeof error.message === "string") {
+		process.stdout.write("\x1B[42m PASSED \x1B[49m\n");
+	} else {
+		process.stdout.write(
+			`\x1B[41m FAILED \x1B[49m\n  Expected code: ${test.code}\n  Got:           ${error && error.code} (${error})\n`,
+		);
+		failed++;
+	}
+});
+
+if (failed == 0) {
+	let passed = ERROR_TESTS.length - failed;
+	console.log(
+		`\n\x1B[32mPassed ${passed} test${passed > 1 ? "s" : ""}!\x1B[39m`,
+	);
+} else {
+	console.log(
+		`\n\x1B[31mFailed ${failed} test${failed > 1 ? "s" : ""}!\x1B[39m`,
+	);
+	process.exit(1);
+}
+
 const CHECK_TESTS = [
 	{
 		title: "Single folder test",
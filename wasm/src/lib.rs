@@ -0,0 +1,5 @@
+//! The WASM build of liquid-docs
+//!
+//! A cdylib can only be linked with `std` so it lives in its own crate, that way `liquid-docs` stays a plain library
+//! that `no_std` projects can depend on. All `#[wasm_bindgen]` exports come from `liquid-docs` itself.
+pub use liquid_docs::*;